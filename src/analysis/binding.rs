@@ -0,0 +1,212 @@
+use super::{Dependency, DependencyTree};
+use crate::elf::sections::Symbol;
+use crate::elf::Elf;
+use crate::utils::Table;
+use std::collections::HashMap;
+
+// symbol binds
+const STB_LOCAL: u8 = 0;
+const STB_WEAK: u8 = 2;
+
+// symbol types
+const STT_SECTION: u8 = 3;
+const STT_FILE: u8 = 4;
+
+// symbol visibilities
+const STV_INTERNAL: u8 = 1;
+const STV_HIDDEN: u8 = 2;
+
+// special version indexes
+const VER_NDX_GLOBAL: u16 = 1;
+const VERSYM_HIDDEN: u16 = 0x8000;
+
+/// Defined dynamic symbols of an object, by name
+type Definitions<'a> = HashMap<&'a str, Vec<&'a Symbol>>;
+
+/// The object an undefined dynamic symbol of the root object binds to
+pub struct ImportBinding {
+    pub name: String,
+    pub version: Option<String>,
+    /// Library named by the version requirement
+    pub expected: Option<String>,
+    pub is_weak: bool,
+    /// Index (in the dependency tree) of the object the symbol binds to
+    pub provider: Option<usize>,
+    /// Indexes of every object which defines a matching symbol, in search order
+    pub definitions: Vec<usize>,
+}
+
+impl ImportBinding {
+    /// Bound to a different library than the one the version requirement names
+    pub fn is_interposed(&self, tree: &DependencyTree) -> bool {
+        match (&self.expected, self.provider) {
+            (Some(expected), Some(provider)) => !provides_name(&tree.objects[provider], expected),
+            _ => false,
+        }
+    }
+}
+
+/// Symbol binding of the root object of a dependency tree, following the breadth first search order of the
+/// dynamic loader: the root object, preloaded libraries, then the libraries in load order
+pub struct BindingReport {
+    pub bindings: Vec<ImportBinding>,
+}
+
+impl BindingReport {
+    pub fn from(tree: &DependencyTree) -> Self {
+        let root = match tree.objects.first().and_then(|object| object.elf.as_ref()) {
+            Some(root) => root,
+            None => {
+                return Self {
+                    bindings: Vec::new(),
+                }
+            }
+        };
+        // defined dynamic symbols of every loaded object, by name
+        let scope: Vec<(usize, &Elf, Definitions)> = tree
+            .objects
+            .iter()
+            .enumerate()
+            .filter_map(|(index, object)| object.elf.as_ref().map(|elf| (index, elf)))
+            .map(|(index, elf)| {
+                let mut definitions = Definitions::new();
+                for symbol in elf.sections.dynsym().unwrap_or(&[]) {
+                    if is_definition(symbol) {
+                        definitions
+                            .entry(symbol.name_or_empty())
+                            .or_default()
+                            .push(symbol);
+                    }
+                }
+                (index, elf, definitions)
+            })
+            .collect();
+
+        let mut bindings = Vec::new();
+        for symbol in root.sections.dynsym().unwrap_or(&[]).iter().skip(1) {
+            let name = symbol.name_or_empty();
+            if !symbol.is_undefined() || name.is_empty() {
+                continue;
+            }
+            let version = root.sections.symbol_version(symbol.index);
+            let requested = version.as_ref().map(|version| version.name.as_str());
+            let definitions: Vec<usize> = scope
+                .iter()
+                .filter(|(_, elf, symbols)| match symbols.get(name) {
+                    Some(candidates) => matching_definition(elf, candidates, requested),
+                    None => false,
+                })
+                .map(|(index, _, _)| *index)
+                .collect();
+            bindings.push(ImportBinding {
+                name: name.to_owned(),
+                expected: version.as_ref().and_then(|version| version.file.clone()),
+                version: version.map(|version| version.name),
+                is_weak: symbol.bind().0 == STB_WEAK,
+                // the first definition wins, whether it is weak or not
+                provider: definitions.first().copied(),
+                definitions,
+            });
+        }
+        Self { bindings }
+    }
+
+    /// Non-weak imports which no loaded object defines
+    pub fn unresolved(&self) -> impl Iterator<Item = &ImportBinding> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.provider.is_none() && !binding.is_weak)
+    }
+
+    /// Imports which more than one loaded object defines
+    pub fn multiply_defined(&self) -> impl Iterator<Item = &ImportBinding> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.definitions.len() > 1)
+    }
+
+    pub fn to_table(&self, tree: &DependencyTree) -> Table {
+        let mut table = Table::new(&["Symbol", "Version", "Bound to", "Also defined in", "Note"]);
+        let name = |index: &usize| tree.objects[*index].name.clone();
+        for binding in &self.bindings {
+            let note = match binding.provider {
+                None if binding.is_weak => String::from("Unresolved weak symbol (resolves to 0)"),
+                None => String::from("Unresolved"),
+                Some(provider) if tree.objects[provider].is_preloaded => {
+                    String::from("Interposed by a preloaded library")
+                }
+                Some(_) if binding.is_interposed(tree) => format!(
+                    "Interposed (required from {})",
+                    binding.expected.as_deref().unwrap_or_default()
+                ),
+                Some(_) => String::new(),
+            };
+            table.push(vec![
+                binding.name.clone(),
+                binding.version.clone().unwrap_or_default(),
+                binding.provider.as_ref().map(name).unwrap_or_default(),
+                binding
+                    .definitions
+                    .iter()
+                    .skip(1)
+                    .map(name)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                note,
+            ]);
+        }
+        table
+    }
+}
+
+/// Whether the object is known by a name, either the DT_NEEDED entry it was loaded for or its soname
+fn provides_name(object: &Dependency, name: &str) -> bool {
+    object.name == name
+        || object
+            .elf
+            .as_ref()
+            .and_then(|elf| elf.sections.dynamic())
+            .and_then(|dynamic| dynamic.soname())
+            == Some(name)
+}
+
+/// Symbols other objects can bind to
+fn is_definition(symbol: &Symbol) -> bool {
+    !symbol.is_undefined()
+        && !symbol.name_or_empty().is_empty()
+        && symbol.bind().0 != STB_LOCAL
+        && !matches!(symbol.sym_type().0, STT_SECTION | STT_FILE)
+        && !matches!(symbol.visibility().0, STV_INTERNAL | STV_HIDDEN)
+}
+
+/// Whether one of the definitions of a name in an object satisfies a reference, following the version checks of
+/// glibc (`check_match`)
+fn matching_definition(elf: &Elf, candidates: &[&Symbol], requested: Option<&str>) -> bool {
+    let versym = match elf.sections.versym() {
+        // objects without version information satisfy any reference
+        None => return true,
+        Some(versym) => versym,
+    };
+    let index = |symbol: &Symbol| versym.versions.get(symbol.index).copied().unwrap_or(0);
+    match requested {
+        // the requested version, or a definition without a version which is not hidden
+        Some(requested) => candidates.iter().any(|symbol| {
+            let value = index(symbol);
+            match elf.sections.symbol_version(symbol.index) {
+                Some(version) => version.name == requested,
+                None => value & !VERSYM_HIDDEN <= VER_NDX_GLOBAL && value & VERSYM_HIDDEN == 0,
+            }
+        }),
+        // unversioned references bind to the base definition, or to the only public version of the symbol
+        None => {
+            let base = candidates
+                .iter()
+                .any(|symbol| index(symbol) & !VERSYM_HIDDEN < 3);
+            let public = candidates
+                .iter()
+                .filter(|symbol| index(symbol) & VERSYM_HIDDEN == 0)
+                .count();
+            base || public == 1
+        }
+    }
+}
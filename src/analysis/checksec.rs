@@ -0,0 +1,623 @@
+use crate::elf::sections::{GnuProperty, SectionType, Symbol};
+use crate::elf::{Description, Elf};
+use crate::utils::Table;
+
+// program header types
+const PT_INTERP: u32 = 3;
+const PT_GNU_STACK: u32 = 0x6474e551;
+const PT_GNU_RELRO: u32 = 0x6474e552;
+
+// dynamic tags and flags
+const DT_RPATH: u64 = 15;
+const DT_BIND_NOW: u64 = 24;
+const DT_RUNPATH: u64 = 29;
+const DT_FLAGS: u64 = 30;
+const DT_FLAGS_1: u64 = 0x6ffffffb;
+const DF_BIND_NOW: u64 = 0x8;
+const DF_1_NOW: u64 = 0x1;
+const DF_1_PIE: u64 = 0x8000000;
+
+/// Functions which have a `__<name>_chk` variant used by `_FORTIFY_SOURCE`
+const FORTIFIABLE: &[&str] = &[
+    "asprintf",
+    "confstr",
+    "dprintf",
+    "explicit_bzero",
+    "fgets",
+    "fgets_unlocked",
+    "fgetws",
+    "fprintf",
+    "fread",
+    "fread_unlocked",
+    "fwprintf",
+    "getcwd",
+    "getdomainname",
+    "getgroups",
+    "gethostname",
+    "getlogin_r",
+    "gets",
+    "getwd",
+    "longjmp",
+    "mbsnrtowcs",
+    "mbsrtowcs",
+    "mbstowcs",
+    "memcpy",
+    "memmove",
+    "mempcpy",
+    "memset",
+    "obstack_printf",
+    "obstack_vprintf",
+    "poll",
+    "ppoll",
+    "pread",
+    "pread64",
+    "printf",
+    "ptsname_r",
+    "read",
+    "readlink",
+    "readlinkat",
+    "realpath",
+    "recv",
+    "recvfrom",
+    "snprintf",
+    "sprintf",
+    "stpcpy",
+    "stpncpy",
+    "strcat",
+    "strcpy",
+    "strncat",
+    "strncpy",
+    "swprintf",
+    "syslog",
+    "ttyname_r",
+    "vasprintf",
+    "vdprintf",
+    "vfprintf",
+    "vfwprintf",
+    "vprintf",
+    "vsnprintf",
+    "vsprintf",
+    "vswprintf",
+    "vsyslog",
+    "vwprintf",
+    "wcpcpy",
+    "wcpncpy",
+    "wcrtomb",
+    "wcscat",
+    "wcscpy",
+    "wcsncat",
+    "wcsncpy",
+    "wcsnrtombs",
+    "wcsrtombs",
+    "wcstombs",
+    "wctomb",
+    "wmemcpy",
+    "wmemmove",
+    "wmempcpy",
+    "wmemset",
+    "wprintf",
+];
+
+/// Whether a check shows the file to be hardened, independently of the feature being present or absent
+pub enum CheckStatus {
+    Hardened,
+    Partial,
+    Weak,
+    NotApplicable,
+}
+
+impl Description for CheckStatus {
+    fn to_str(&self) -> String {
+        match self {
+            CheckStatus::Hardened => "Hardened".to_owned(),
+            CheckStatus::Partial => "Partial".to_owned(),
+            CheckStatus::Weak => "Weak".to_owned(),
+            CheckStatus::NotApplicable => "N/A".to_owned(),
+        }
+    }
+}
+
+/// The part of the file a verdict is based on
+pub enum Evidence {
+    ElfHeader,
+    ProgramHeader(usize),
+    /// Index of the DYNAMIC section and of the entry in it
+    DynamicEntry {
+        section: usize,
+        index: usize,
+    },
+    /// Index of the symbol table section and of the symbol in it
+    Symbol {
+        section: usize,
+        index: usize,
+    },
+    Note {
+        section: usize,
+    },
+    /// Something that was looked for but not found
+    Absent(String),
+}
+
+impl Evidence {
+    pub fn describe(&self, elf: &Elf) -> String {
+        let section_name = |index: usize| {
+            elf.sections
+                .0
+                .get(index)
+                .and_then(|section| section.name.to_owned())
+                .unwrap_or_else(|| format!("section {}", index))
+        };
+        match self {
+            Evidence::ElfHeader => format!("ELF header: e_type {}", elf.hdr.e_type.to_str()),
+            Evidence::ProgramHeader(index) => {
+                let segments = elf.phdr_table.segments();
+                match segments.get(*index) {
+                    Some(segment) => format!(
+                        "Program header {} ({}, flags 0x{:x})",
+                        index,
+                        segment.type_name(),
+                        segment.p_flags
+                    ),
+                    None => format!("Program header {}", index),
+                }
+            }
+            Evidence::DynamicEntry { section, index } => {
+                let entry =
+                    elf.sections
+                        .0
+                        .get(*section)
+                        .and_then(|section| match &section.section_type {
+                            SectionType::Dynamic(dynamic) => dynamic.entries.get(*index),
+                            _ => None,
+                        });
+                match entry {
+                    Some(entry) => format!(
+                        "{} entry {}: {} {}",
+                        section_name(*section),
+                        index,
+                        entry.d_tag.to_str(),
+                        entry.value_str()
+                    ),
+                    None => format!("{} entry {}", section_name(*section), index),
+                }
+            }
+            Evidence::Symbol { section, index } => {
+                let symbol =
+                    elf.sections
+                        .0
+                        .get(*section)
+                        .and_then(|section| match &section.section_type {
+                            SectionType::Symtab(symtab) => symtab.symbols.get(*index),
+                            _ => None,
+                        });
+                format!(
+                    "{} symbol {}: {}",
+                    section_name(*section),
+                    index,
+                    symbol.map(|symbol| symbol.name_or_empty()).unwrap_or("")
+                )
+            }
+            Evidence::Note { section } => format!("{} note", section_name(*section)),
+            Evidence::Absent(what) => format!("No {}", what),
+        }
+    }
+}
+
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub details: String,
+    pub evidence: Vec<Evidence>,
+}
+
+/// checksec style summary of the hardening features of a file
+pub struct HardeningReport {
+    pub checks: Vec<Check>,
+}
+
+impl HardeningReport {
+    pub fn from(elf: &Elf) -> Self {
+        let checks = vec![
+            Self::relro(elf),
+            Self::nx(elf),
+            Self::pie(elf),
+            Self::canary(elf),
+            Self::fortify(elf),
+            Self::cet(elf),
+            Self::rpath(elf, DT_RPATH, "RPATH"),
+            Self::rpath(elf, DT_RUNPATH, "RUNPATH"),
+        ];
+        Self { checks }
+    }
+
+    fn dynamic_entry(elf: &Elf, tag: u64) -> Option<(Evidence, u64)> {
+        for section in &elf.sections.0 {
+            if let SectionType::Dynamic(dynamic) = &section.section_type {
+                let found = dynamic
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .find(|(_, entry)| entry.d_tag.0 == tag);
+                if let Some((index, entry)) = found {
+                    let evidence = Evidence::DynamicEntry {
+                        section: section.index,
+                        index,
+                    };
+                    return Some((evidence, entry.d_val));
+                }
+            }
+        }
+        None
+    }
+
+    // symbols are looked up in DYNSYM first, then in SYMTAB
+    fn find_symbols(elf: &Elf) -> Vec<(usize, &Symbol)> {
+        let mut symbols = Vec::new();
+        for sh_type in [11, 2] {
+            for section in elf.sections.0.iter().filter(|s| s.sh_type() == sh_type) {
+                if let SectionType::Symtab(symtab) = &section.section_type {
+                    symbols.extend(symtab.symbols.iter().map(|symbol| (section.index, symbol)));
+                }
+            }
+        }
+        symbols
+    }
+
+    // relocatable files and files without program headers are not loaded as they are
+    fn not_loadable(elf: &Elf, name: &'static str) -> Option<Check> {
+        match elf.hdr.e_type.0 == 1 || elf.hdr.e_phnum == 0 {
+            true => Some(Check {
+                name,
+                status: CheckStatus::NotApplicable,
+                details: "The file has no program headers".to_owned(),
+                evidence: vec![Evidence::ElfHeader],
+            }),
+            false => None,
+        }
+    }
+
+    fn relro(elf: &Elf) -> Check {
+        if let Some(check) = Self::not_loadable(elf, "RELRO") {
+            return check;
+        }
+        let segments = elf.phdr_table.segments();
+        let relro = segments
+            .iter()
+            .find(|segment| segment.p_type == PT_GNU_RELRO);
+        let mut evidence = Vec::new();
+
+        let bind_now = Self::dynamic_entry(elf, DT_BIND_NOW)
+            .map(|(evidence, _)| evidence)
+            .or_else(|| {
+                Self::dynamic_entry(elf, DT_FLAGS)
+                    .filter(|(_, flags)| flags & DF_BIND_NOW != 0)
+                    .map(|(evidence, _)| evidence)
+            })
+            .or_else(|| {
+                Self::dynamic_entry(elf, DT_FLAGS_1)
+                    .filter(|(_, flags)| flags & DF_1_NOW != 0)
+                    .map(|(evidence, _)| evidence)
+            });
+
+        let (status, details) = match (relro, bind_now) {
+            (None, _) => {
+                evidence.push(Evidence::Absent("GNU_RELRO program header".to_owned()));
+                (CheckStatus::Weak, "No RELRO")
+            }
+            (Some(relro), None) => {
+                evidence.push(Evidence::ProgramHeader(relro.index));
+                evidence.push(Evidence::Absent("BIND_NOW dynamic flag".to_owned()));
+                (
+                    CheckStatus::Partial,
+                    "Partial RELRO (the GOT used for lazy binding stays writable)",
+                )
+            }
+            (Some(relro), Some(bind_now)) => {
+                evidence.push(Evidence::ProgramHeader(relro.index));
+                evidence.push(bind_now);
+                (CheckStatus::Hardened, "Full RELRO")
+            }
+        };
+        Check {
+            name: "RELRO",
+            status,
+            details: details.to_owned(),
+            evidence,
+        }
+    }
+
+    fn nx(elf: &Elf) -> Check {
+        if let Some(check) = Self::not_loadable(elf, "NX") {
+            return check;
+        }
+        let segments = elf.phdr_table.segments();
+        let (status, details, evidence) = match segments
+            .iter()
+            .find(|segment| segment.p_type == PT_GNU_STACK)
+        {
+            Some(stack) if stack.p_flags & 1 == 0 => (
+                CheckStatus::Hardened,
+                "The stack is not executable",
+                Evidence::ProgramHeader(stack.index),
+            ),
+            Some(stack) => (
+                CheckStatus::Weak,
+                "The stack is executable",
+                Evidence::ProgramHeader(stack.index),
+            ),
+            None => (
+                CheckStatus::Weak,
+                "The stack is executable by default when there is no GNU_STACK program header",
+                Evidence::Absent("GNU_STACK program header".to_owned()),
+            ),
+        };
+        Check {
+            name: "NX",
+            status,
+            details: details.to_owned(),
+            evidence: vec![evidence],
+        }
+    }
+
+    fn pie(elf: &Elf) -> Check {
+        let mut evidence = vec![Evidence::ElfHeader];
+        let (status, details) = match elf.hdr.e_type.0 {
+            // EXEC
+            2 => (CheckStatus::Weak, "Not PIE, loaded at a fixed address"),
+            // DYN
+            3 => {
+                let pie = Self::dynamic_entry(elf, DT_FLAGS_1)
+                    .filter(|(_, flags)| flags & DF_1_PIE != 0)
+                    .map(|(evidence, _)| evidence);
+                let interp = elf
+                    .phdr_table
+                    .segments()
+                    .iter()
+                    .find(|segment| segment.p_type == PT_INTERP)
+                    .map(|segment| Evidence::ProgramHeader(segment.index));
+                match (pie, interp) {
+                    (Some(pie), _) => {
+                        evidence.push(pie);
+                        (CheckStatus::Hardened, "Position independent executable")
+                    }
+                    // older linkers don't set DF_1_PIE, but only executables request an interpreter
+                    (None, Some(interp)) => {
+                        evidence.push(interp);
+                        (CheckStatus::Hardened, "Position independent executable")
+                    }
+                    (None, None) => (CheckStatus::NotApplicable, "Shared object"),
+                }
+            }
+            _ => (CheckStatus::NotApplicable, "Not an executable"),
+        };
+        Check {
+            name: "PIE",
+            status,
+            details: details.to_owned(),
+            evidence,
+        }
+    }
+
+    fn canary(elf: &Elf) -> Check {
+        let symbols = Self::find_symbols(elf);
+        // libc defines these symbols, only importing them means the code is instrumented
+        let found = symbols.iter().find(|(_, symbol)| {
+            symbol.is_undefined()
+                && matches!(
+                    symbol.name_or_empty(),
+                    "__stack_chk_fail" | "__stack_chk_guard" | "__intel_security_cookie"
+                )
+        });
+        let (status, details, evidence) = match found {
+            Some((section, symbol)) => (
+                CheckStatus::Hardened,
+                format!("Stack protector found ({})", symbol.name_or_empty()),
+                Evidence::Symbol {
+                    section: *section,
+                    index: symbol.index,
+                },
+            ),
+            None => (
+                CheckStatus::Weak,
+                "No stack protector symbols imported".to_owned(),
+                Evidence::Absent("__stack_chk_fail import".to_owned()),
+            ),
+        };
+        Check {
+            name: "Stack canary",
+            status,
+            details,
+            evidence: vec![evidence],
+        }
+    }
+
+    fn fortify(elf: &Elf) -> Check {
+        let symbols = Self::find_symbols(elf);
+        let mut fortified = Vec::new();
+        let mut fortifiable = Vec::new();
+        let mut evidence = Vec::new();
+
+        for (section, symbol) in symbols.iter().filter(|(_, symbol)| symbol.is_undefined()) {
+            let name = symbol.name_or_empty();
+            let base = name
+                .strip_prefix("__")
+                .and_then(|name| name.strip_suffix("_chk"))
+                .filter(|base| FORTIFIABLE.contains(base));
+            let (list, function) = match (base, FORTIFIABLE.contains(&name)) {
+                (Some(base), _) => (&mut fortified, base),
+                (None, true) => (&mut fortifiable, name),
+                (None, false) => continue,
+            };
+            if list.contains(&function) {
+                continue;
+            }
+            list.push(function);
+            evidence.push(Evidence::Symbol {
+                section: *section,
+                index: symbol.index,
+            });
+        }
+
+        let status = match (fortified.len(), fortifiable.len()) {
+            (0, 0) => CheckStatus::NotApplicable,
+            (0, _) => CheckStatus::Weak,
+            (_, 0) => CheckStatus::Hardened,
+            _ => CheckStatus::Partial,
+        };
+        let details = match status {
+            CheckStatus::NotApplicable => "No fortifiable functions are used".to_owned(),
+            _ => {
+                let list = |functions: &Vec<&str>| match functions.is_empty() {
+                    true => "".to_owned(),
+                    false => format!(" ({})", functions.join(", ")),
+                };
+                format!(
+                    "{} fortified{}, {} not fortified{}",
+                    fortified.len(),
+                    list(&fortified),
+                    fortifiable.len(),
+                    list(&fortifiable)
+                )
+            }
+        };
+        Check {
+            name: "FORTIFY_SOURCE",
+            status,
+            details,
+            evidence,
+        }
+    }
+
+    fn cet(elf: &Elf) -> Check {
+        let (name, features) =
+            match elf.hdr.e_machine.0 {
+                // i386, x86-64
+                3 | 62 => ("CET", [(1, "IBT"), (2, "SHSTK")]),
+                // AArch64
+                183 => ("BTI/PAC", [(1, "BTI"), (2, "PAC")]),
+                _ => return Check {
+                    name: "CET",
+                    status: CheckStatus::NotApplicable,
+                    details:
+                        "Control flow protection properties are not defined for this architecture"
+                            .to_owned(),
+                    evidence: Vec::new(),
+                },
+            };
+        // X86_FEATURE_1_AND, AARCH64_FEATURE_1_AND
+        let pr_type = match elf.hdr.e_machine.0 {
+            183 => 0xc0000000,
+            _ => 0xc0000002,
+        };
+
+        let mut found: Option<(usize, u64)> = None;
+        for section in &elf.sections.0 {
+            if let SectionType::Note(notes) = &section.section_type {
+                let property = notes
+                    .notes
+                    .iter()
+                    .flat_map(|note| note.gnu_properties())
+                    .find(|property: &GnuProperty| property.pr_type.0 == pr_type);
+                if let Some(property) = property {
+                    found = Some((section.index, property.bits()));
+                    break;
+                }
+            }
+        }
+
+        let (bits, evidence) = match found {
+            Some((section, bits)) => (bits, Evidence::Note { section }),
+            None => (
+                0,
+                Evidence::Absent("GNU property note with feature flags".to_owned()),
+            ),
+        };
+        let enabled: Vec<&str> = features
+            .iter()
+            .filter(|(bit, _)| bits & bit != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        let missing: Vec<&str> = features
+            .iter()
+            .filter(|(bit, _)| bits & bit == 0)
+            .map(|(_, name)| *name)
+            .collect();
+        let status = match (enabled.len(), missing.len()) {
+            (_, 0) => CheckStatus::Hardened,
+            (0, _) => CheckStatus::Weak,
+            _ => CheckStatus::Partial,
+        };
+        let details = match (enabled.is_empty(), missing.is_empty()) {
+            (true, _) => format!("{} not marked", missing.join(", ")),
+            (false, true) => format!("{} marked", enabled.join(", ")),
+            (false, false) => format!(
+                "{} marked, {} not marked",
+                enabled.join(", "),
+                missing.join(", ")
+            ),
+        };
+        Check {
+            name,
+            status,
+            details,
+            evidence: vec![evidence],
+        }
+    }
+
+    fn rpath(elf: &Elf, tag: u64, name: &'static str) -> Check {
+        let found = elf
+            .sections
+            .0
+            .iter()
+            .find_map(|section| match &section.section_type {
+                SectionType::Dynamic(dynamic) => dynamic
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .find(|(_, entry)| entry.d_tag.0 == tag)
+                    .map(|(index, entry)| (section.index, index, entry.string.clone())),
+                _ => None,
+            });
+        let (status, details, evidence) = match found {
+            // search paths embedded in the file can be abused to load attacker controlled libraries
+            Some((section, index, path)) => (
+                CheckStatus::Weak,
+                format!(
+                    "{} is set to {}",
+                    name,
+                    path.unwrap_or_else(|| "<unknown>".to_owned())
+                ),
+                Evidence::DynamicEntry { section, index },
+            ),
+            None => (
+                CheckStatus::Hardened,
+                format!("No {}", name),
+                Evidence::Absent(format!("{} dynamic entry", name)),
+            ),
+        };
+        Check {
+            name,
+            status,
+            details,
+            evidence: vec![evidence],
+        }
+    }
+
+    pub fn to_table(&self, elf: &Elf) -> Table {
+        let mut table = Table::new(&["Check", "Status", "Details", "Evidence"]);
+        for check in &self.checks {
+            table.push(vec![
+                check.name.to_owned(),
+                check.status.to_str(),
+                check.details.to_owned(),
+                check
+                    .evidence
+                    .iter()
+                    .map(|evidence| evidence.describe(elf))
+                    .collect::<Vec<String>>()
+                    .join("; "),
+            ]);
+        }
+        table
+    }
+}
@@ -0,0 +1,440 @@
+use super::LdCache;
+use crate::elf::{Description, Elf};
+use crate::utils::Table;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// executable types
+const ET_DYN: u16 = 3;
+
+/// Where the dynamic loader looks for libraries, and how to expand the tokens of `DT_RPATH`/`DT_RUNPATH`
+pub struct SearchConfig {
+    /// Root of the target filesystem, prepended to every absolute path
+    pub sysroot: PathBuf,
+    /// Directories searched after DT_RUNPATH, like `LD_LIBRARY_PATH`
+    pub library_path: Vec<String>,
+    /// Directories searched last, like the loader's built-in system directories
+    pub default_paths: Vec<String>,
+    /// Expansion of `$LIB`
+    pub lib: String,
+    /// Expansion of `$PLATFORM`
+    pub platform: String,
+    /// Contents of `/etc/ld.so.cache`, consulted before the default paths
+    pub cache: Option<LdCache>,
+    /// Libraries loaded before the DT_NEEDED entries, like `LD_PRELOAD`
+    pub preload: Vec<String>,
+}
+
+impl SearchConfig {
+    /// Defaults of a glibc system for the class and machine of the file
+    pub fn for_elf(elf: &Elf, sysroot: &Path) -> Self {
+        let (triplet, platform) = match (elf.hdr.e_machine.0, elf.is_little_endian()) {
+            (3, _) => (Some("i386-linux-gnu"), "i686"),
+            (62, _) => (Some("x86_64-linux-gnu"), "x86_64"),
+            (183, _) => (Some("aarch64-linux-gnu"), "aarch64"),
+            (40, _) => (Some("arm-linux-gnueabihf"), "v7l"),
+            (21, true) => (Some("powerpc64le-linux-gnu"), "power8"),
+            (22, _) => (Some("s390x-linux-gnu"), "z196"),
+            (243, _) => (Some("riscv64-linux-gnu"), "riscv64"),
+            _ => (None, ""),
+        };
+        let lib = match elf.is_64_bit() {
+            true => "lib64",
+            false => "lib",
+        };
+
+        let mut default_paths = Vec::new();
+        if let Some(triplet) = triplet {
+            default_paths.push(format!("/lib/{}", triplet));
+            default_paths.push(format!("/usr/lib/{}", triplet));
+        }
+        if elf.is_64_bit() {
+            default_paths.push(String::from("/lib64"));
+            default_paths.push(String::from("/usr/lib64"));
+        }
+        default_paths.push(String::from("/lib"));
+        default_paths.push(String::from("/usr/lib"));
+
+        Self {
+            sysroot: sysroot.to_owned(),
+            library_path: Vec::new(),
+            default_paths,
+            lib: lib.to_owned(),
+            platform: platform.to_owned(),
+            cache: None,
+            preload: Vec::new(),
+        }
+    }
+
+    /// Path of a file of the target filesystem on the host
+    pub fn host_path(&self, path: &str) -> PathBuf {
+        self.sysroot.join(path.trim_start_matches('/'))
+    }
+
+    /// Expand `$ORIGIN`, `$LIB` and `$PLATFORM` (or `${...}`) in a search path entry
+    fn expand(&self, entry: &str, origin: &Path) -> PathBuf {
+        let mut expanded = entry.to_owned();
+        for (token, value) in [
+            ("LIB", self.lib.as_str()),
+            ("PLATFORM", self.platform.as_str()),
+        ] {
+            expanded = expanded
+                .replace(&format!("${{{}}}", token), value)
+                .replace(&format!("${}", token), value);
+        }
+        // $ORIGIN is already a host path, other entries are paths of the target filesystem
+        for token in ["${ORIGIN}", "$ORIGIN"] {
+            if let Some(rest) = expanded.strip_prefix(token) {
+                let rest = rest.replace("${ORIGIN}", "").replace("$ORIGIN", "");
+                return origin.join(rest.trim_start_matches('/'));
+            }
+        }
+        self.host_path(&expanded)
+    }
+}
+
+/// The step of the search order a library was found in
+#[derive(Clone, Copy, PartialEq)]
+pub enum SearchSource {
+    /// The file itself, or a DT_NEEDED entry containing a slash
+    Direct,
+    Rpath,
+    LibraryPath,
+    Runpath,
+    Cache,
+    Default,
+}
+
+impl Description for SearchSource {
+    fn to_str(&self) -> String {
+        match self {
+            Self::Direct => "direct",
+            Self::Rpath => "DT_RPATH",
+            Self::LibraryPath => "LD_LIBRARY_PATH",
+            Self::Runpath => "DT_RUNPATH",
+            Self::Cache => "ld.so.cache",
+            Self::Default => "default path",
+        }
+        .to_owned()
+    }
+}
+
+pub enum DependencyStatus {
+    Found {
+        path: PathBuf,
+        source: SearchSource,
+    },
+    Missing,
+    /// Only files which can't be loaded by the root object were found
+    Incompatible {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+/// A loaded object (or a DT_NEEDED entry which could not be loaded)
+pub struct Dependency {
+    /// The DT_NEEDED name (or the path of the root object)
+    pub name: String,
+    pub status: DependencyStatus,
+    pub elf: Option<Elf>,
+    /// Index of the object which first needed this one
+    pub parent: Option<usize>,
+    /// Indexes of the objects named by the DT_NEEDED entries, in order
+    pub needed: Vec<usize>,
+    /// Loaded because of `LD_PRELOAD` rather than a DT_NEEDED entry
+    pub is_preloaded: bool,
+}
+
+impl Dependency {
+    pub fn path(&self) -> Option<&Path> {
+        match &self.status {
+            DependencyStatus::Found { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// Libraries loaded for a file, resolved the way the dynamic loader would without executing anything.
+/// Objects are stored in load order (breadth first), the root object comes first
+pub struct DependencyTree {
+    pub objects: Vec<Dependency>,
+}
+
+impl DependencyTree {
+    pub fn from(path: &Path, elf: Elf, config: &SearchConfig) -> Self {
+        let mut objects = vec![Dependency {
+            name: path.to_string_lossy().into_owned(),
+            status: DependencyStatus::Found {
+                path: path.to_owned(),
+                source: SearchSource::Direct,
+            },
+            elf: Some(elf),
+            parent: None,
+            needed: Vec::new(),
+            is_preloaded: false,
+        }];
+
+        let mut queue = VecDeque::from([0]);
+        // preloaded libraries come right after the root object in the search order
+        for name in &config.preload {
+            let loaded = Self::load(&mut objects, 0, name, config, &mut queue);
+            objects[loaded].is_preloaded = true;
+        }
+        while let Some(index) = queue.pop_front() {
+            let needed: Vec<String> = match objects[index].elf.as_ref() {
+                Some(elf) => match elf.sections.dynamic() {
+                    Some(dynamic) => dynamic
+                        .needed()
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect(),
+                    None => Vec::new(),
+                },
+                None => Vec::new(),
+            };
+            for name in needed {
+                let loaded = Self::load(&mut objects, index, &name, config, &mut queue);
+                objects[index].needed.push(loaded);
+            }
+        }
+        Self { objects }
+    }
+
+    /// Load a library requested by `objects[index]`, unless it is already loaded, and return its index
+    fn load(
+        objects: &mut Vec<Dependency>,
+        index: usize,
+        name: &str,
+        config: &SearchConfig,
+        queue: &mut VecDeque<usize>,
+    ) -> usize {
+        // the loader loads every library once, matching by name and soname
+        if let Some(existing) = Self::find_loaded(objects, name) {
+            return existing;
+        }
+        let (status, elf) = Self::search(objects, index, name, config);
+        if let DependencyStatus::Found { path, .. } = &status {
+            if let Some(existing) = objects
+                .iter()
+                .position(|object| object.path() == Some(path))
+            {
+                return existing;
+            }
+        }
+        let is_loaded = elf.is_some();
+        objects.push(Dependency {
+            name: name.to_owned(),
+            status,
+            elf,
+            parent: Some(index),
+            needed: Vec::new(),
+            is_preloaded: false,
+        });
+        let new = objects.len() - 1;
+        if is_loaded {
+            queue.push_back(new);
+        }
+        new
+    }
+
+    fn find_loaded(objects: &[Dependency], name: &str) -> Option<usize> {
+        objects.iter().position(|object| {
+            object.name == name
+                || object
+                    .elf
+                    .as_ref()
+                    .and_then(|elf| elf.sections.dynamic())
+                    .and_then(|dynamic| dynamic.soname())
+                    == Some(name)
+        })
+    }
+
+    /// Paths to try for a library needed by `objects[index]`, in the order of the dynamic loader
+    fn candidates(
+        objects: &[Dependency],
+        index: usize,
+        name: &str,
+        config: &SearchConfig,
+    ) -> Vec<(PathBuf, SearchSource)> {
+        if name.contains('/') {
+            return vec![(config.host_path(name), SearchSource::Direct)];
+        }
+        let mut dirs = Vec::new();
+        let dynamic = |index: usize| {
+            objects[index]
+                .elf
+                .as_ref()
+                .and_then(|elf| elf.sections.dynamic())
+        };
+        let origin = |index: usize| {
+            objects[index]
+                .path()
+                .and_then(|path| path.parent())
+                .map(|parent| parent.to_owned())
+                .unwrap_or_default()
+        };
+        let split = |list: &str, origin: &Path, source: SearchSource| {
+            list.split(':')
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| (config.expand(entry, origin), source))
+                .collect::<Vec<_>>()
+        };
+
+        // DT_RPATH of the object and of the objects which loaded it, unless the object has DT_RUNPATH
+        let runpath = dynamic(index).and_then(|dynamic| dynamic.runpath());
+        if runpath.is_none() {
+            let mut current = Some(index);
+            while let Some(loader) = current {
+                if let Some(dynamic) = dynamic(loader) {
+                    if let (Some(rpath), None) = (dynamic.rpath(), dynamic.runpath()) {
+                        dirs.extend(split(rpath, &origin(loader), SearchSource::Rpath));
+                    }
+                }
+                current = objects[loader].parent;
+            }
+        }
+        for entry in &config.library_path {
+            dirs.push((config.host_path(entry), SearchSource::LibraryPath));
+        }
+        if let Some(runpath) = runpath {
+            dirs.extend(split(runpath, &origin(index), SearchSource::Runpath));
+        }
+        let mut candidates: Vec<(PathBuf, SearchSource)> = dirs
+            .into_iter()
+            .map(|(dir, source)| (dir.join(name), source))
+            .collect();
+
+        // the cache is consulted before the default directories
+        let root = objects[0].elf.as_ref().unwrap();
+        if let Some(entry) = config
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.lookup(name, root))
+        {
+            candidates.push((config.host_path(&entry.value), SearchSource::Cache));
+        }
+        for entry in &config.default_paths {
+            candidates.push((config.host_path(entry).join(name), SearchSource::Default));
+        }
+        candidates
+    }
+
+    fn search(
+        objects: &[Dependency],
+        index: usize,
+        name: &str,
+        config: &SearchConfig,
+    ) -> (DependencyStatus, Option<Elf>) {
+        let root = objects[0].elf.as_ref().unwrap();
+        let candidates = Self::candidates(objects, index, name, config);
+
+        let mut incompatible = None;
+        for (path, source) in candidates {
+            let raw = match fs::read(&path) {
+                Ok(raw) => raw,
+                Err(_) => continue,
+            };
+            // files which can't be loaded are skipped, and the search goes on
+            let reason = match Elf::from(raw) {
+                Err(_) => String::from("not an ELF file"),
+                Ok(elf) => match Self::incompatibility(root, &elf) {
+                    None => return (DependencyStatus::Found { path, source }, Some(elf)),
+                    Some(reason) => reason,
+                },
+            };
+            if incompatible.is_none() {
+                incompatible = Some(DependencyStatus::Incompatible { path, reason });
+            }
+        }
+        (incompatible.unwrap_or(DependencyStatus::Missing), None)
+    }
+
+    /// Why a library can't be loaded into the process of the root object, if it can't
+    pub fn incompatibility(root: &Elf, library: &Elf) -> Option<String> {
+        if root.is_64_bit() != library.is_64_bit() {
+            return Some(format!(
+                "wrong class ({})",
+                library.hdr.e_ident.ei_class.to_str()
+            ));
+        }
+        if root.is_little_endian() != library.is_little_endian() {
+            return Some(format!(
+                "wrong byte order ({})",
+                library.hdr.e_ident.ei_data.to_str()
+            ));
+        }
+        if root.hdr.e_machine.0 != library.hdr.e_machine.0 {
+            return Some(format!(
+                "wrong machine ({})",
+                library.hdr.e_machine.to_str()
+            ));
+        }
+        if library.hdr.e_type.0 != ET_DYN {
+            return Some(format!(
+                "not a shared object ({})",
+                library.hdr.e_type.to_str()
+            ));
+        }
+        None
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &Dependency> {
+        self.objects
+            .iter()
+            .filter(|object| matches!(object.status, DependencyStatus::Missing))
+    }
+
+    pub fn incompatible(&self) -> impl Iterator<Item = &Dependency> {
+        self.objects
+            .iter()
+            .filter(|object| matches!(object.status, DependencyStatus::Incompatible { .. }))
+    }
+
+    /// The tree of DT_NEEDED entries, like `ldd` but with libraries which were already loaded marked as such
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Library", "Path", "Found in", "Status"]);
+        let mut visited = vec![false; self.objects.len()];
+        self.push_rows(&mut table, 0, 0, &mut visited);
+        table
+    }
+
+    fn push_rows(&self, table: &mut Table, index: usize, depth: usize, visited: &mut [bool]) {
+        let object = &self.objects[index];
+        let name = format!("{}{}", "    ".repeat(depth), object.name);
+        let (path, source, status) = match &object.status {
+            DependencyStatus::Found { path, source } => (
+                path.to_string_lossy().into_owned(),
+                source.to_str(),
+                match (visited[index], object.is_preloaded) {
+                    (true, _) => String::from("Already loaded"),
+                    (false, true) => String::from("Preloaded"),
+                    (false, false) => String::from("Found"),
+                },
+            ),
+            DependencyStatus::Missing => (String::new(), String::new(), String::from("Not found")),
+            DependencyStatus::Incompatible { path, reason } => (
+                path.to_string_lossy().into_owned(),
+                String::new(),
+                format!("Incompatible: {}", reason),
+            ),
+        };
+        table.push(vec![name, path, source, status]);
+        if visited[index] {
+            return;
+        }
+        visited[index] = true;
+        // preloaded libraries are shown under the root object, before its DT_NEEDED entries
+        let preloaded = self
+            .objects
+            .iter()
+            .enumerate()
+            .filter(|(_, object)| index == 0 && object.is_preloaded)
+            .map(|(preloaded, _)| preloaded);
+        let children: Vec<usize> = preloaded.chain(object.needed.iter().copied()).collect();
+        for child in children {
+            self.push_rows(table, child, depth + 1, visited);
+        }
+    }
+}
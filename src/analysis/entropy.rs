@@ -0,0 +1,151 @@
+use super::FileLayout;
+use crate::elf::{shannon_entropy, Elf, RegionEntropy, HIGH_ENTROPY};
+use crate::utils::Table;
+
+/// Smallest window of the entropy profile, the window grows with the file to keep the profile short
+const MIN_WINDOW_SIZE: usize = 1024;
+
+/// Number of windows the profile aims for
+const PROFILE_WINDOWS: usize = 512;
+
+/// Entropy of a window of the sliding window profile
+pub struct EntropyWindow {
+    pub offset: u64,
+    pub size: u64,
+    pub entropy: f64,
+    /// The window lies in the file image of an executable PT_LOAD segment
+    pub is_executable: bool,
+}
+
+/// Shannon entropy of the sections, segments and overlay of the file, and an entropy profile across the whole file
+pub struct EntropyReport {
+    pub file: RegionEntropy,
+    pub sections: Vec<RegionEntropy>,
+    pub segments: Vec<RegionEntropy>,
+    /// Data after the last structure of the file
+    pub overlay: Option<RegionEntropy>,
+    pub window_size: usize,
+    pub profile: Vec<EntropyWindow>,
+}
+
+impl EntropyReport {
+    pub fn from(elf: &Elf) -> Self {
+        let data = elf.raw.get();
+        let overlay = FileLayout::from(elf).trailing_data().map(|region| {
+            RegionEntropy::from(
+                String::from("Overlay"),
+                &data[region.start as usize..region.end as usize],
+                region.start,
+                false,
+            )
+        });
+
+        // windows overlap by half, so that a high entropy block is not split between two windows
+        let window_size = (data.len() / PROFILE_WINDOWS)
+            .max(MIN_WINDOW_SIZE)
+            .next_power_of_two();
+        let step = window_size / 2;
+        let executable: Vec<(u64, u64)> = elf
+            .phdr_table
+            .segments()
+            .iter()
+            .filter(|segment| segment.is_executable())
+            .map(|segment| {
+                (
+                    segment.offset,
+                    segment.offset.saturating_add(segment.filesz),
+                )
+            })
+            .collect();
+        let mut profile = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let end = (offset + window_size).min(data.len());
+            let (start, end) = (offset as u64, end as u64);
+            profile.push(EntropyWindow {
+                offset: start,
+                size: end - start,
+                entropy: shannon_entropy(&data[offset..end as usize]),
+                is_executable: executable.iter().any(|(s, e)| start < *e && *s < end),
+            });
+            if end as usize == data.len() {
+                break;
+            }
+            offset += step;
+        }
+
+        Self {
+            file: RegionEntropy::from(String::from("Whole file"), data, 0, false),
+            sections: elf.sections.entropy(),
+            segments: elf.phdr_table.entropy(data),
+            overlay,
+            window_size,
+            profile,
+        }
+    }
+
+    /// Whether an executable section, segment or profile window has the entropy of compressed or encrypted data
+    pub fn is_likely_packed(&self) -> bool {
+        self.sections
+            .iter()
+            .any(|section| section.is_likely_packed())
+            || self
+                .segments
+                .iter()
+                .any(|segment| segment.is_likely_packed())
+            || self
+                .profile
+                .iter()
+                .any(|window| window.is_executable && window.entropy >= HIGH_ENTROPY)
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Kind", "Name", "Offset", "Size", "Entropy", "Note"]);
+        let mut push = |kind: &str, region: &RegionEntropy| {
+            let note = match (region.is_likely_packed(), region.is_high()) {
+                (true, _) => "High entropy executable code (likely packed or encrypted)",
+                (false, true) => "High entropy (compressed or encrypted data)",
+                _ => "",
+            };
+            table.push(vec![
+                kind.to_owned(),
+                region.name.clone(),
+                format!("0x{:x}", region.offset),
+                format!("0x{:x}", region.size),
+                format!("{:.3}", region.entropy),
+                note.to_owned(),
+            ]);
+        };
+        push("File", &self.file);
+        for section in &self.sections {
+            push("Section", section);
+        }
+        for segment in &self.segments {
+            push("Segment", segment);
+        }
+        if let Some(overlay) = &self.overlay {
+            push("Overlay", overlay);
+        }
+        table
+    }
+
+    /// Sliding window profile, with a bar to spot high entropy areas at a glance
+    pub fn profile_table(&self) -> Table {
+        let mut table = Table::new(&["Offset", "Size", "Entropy", "Profile", "Note"]);
+        for window in &self.profile {
+            let note = match (window.entropy >= HIGH_ENTROPY, window.is_executable) {
+                (true, true) => "High entropy executable code (likely packed or encrypted)",
+                (true, false) => "High entropy",
+                _ => "",
+            };
+            table.push(vec![
+                format!("0x{:x}", window.offset),
+                format!("0x{:x}", window.size),
+                format!("{:.3}", window.entropy),
+                "#".repeat((window.entropy * 4.0).round() as usize),
+                note.to_owned(),
+            ]);
+        }
+        table
+    }
+}
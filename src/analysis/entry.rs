@@ -0,0 +1,150 @@
+use crate::elf::{Description, Elf, Location, PFlags, Segment};
+use crate::utils::{self, Table};
+
+// executable types
+const ET_REL: u16 = 1;
+const ET_EXEC: u16 = 2;
+
+// segment flags
+const PF_X: u32 = 0x1;
+const PF_W: u32 = 0x2;
+
+/// Number of bytes shown at the entry point
+const ENTRY_BYTES: usize = 16;
+
+/// Where the entry point of the file lands, and anything unusual about it
+pub struct EntryPoint {
+    pub address: u64,
+    pub location: Option<Location>,
+    pub segment: Option<Segment>,
+    pub section: Option<String>,
+    pub symbol: Option<String>,
+    pub bytes: Vec<u8>,
+    pub warnings: Vec<String>,
+}
+
+impl EntryPoint {
+    /// Resolve the entry point. Returns None for files which have no entry point (`e_entry` is 0 outside of executables)
+    pub fn from(elf: &Elf) -> Option<Self> {
+        let address = elf.hdr.e_entry.to_u64();
+        let e_type = elf.hdr.e_type.0;
+        if address == 0 && e_type != ET_EXEC {
+            return None;
+        }
+
+        let location = match e_type {
+            ET_REL => None,
+            _ => elf.locate_vaddr(address),
+        };
+        let segment = location
+            .as_ref()
+            .and_then(|location| location.segment)
+            .and_then(|index| elf.phdr_table.segments().into_iter().nth(index));
+        let section = location
+            .as_ref()
+            .and_then(|location| location.section)
+            .map(|index| elf.sections.0[index].name.clone().unwrap_or_default());
+        let symbol = elf.symbolize(address);
+        let offset = location.as_ref().and_then(|location| location.offset);
+        // the segment may claim file contents past the end of a truncated or malformed file
+        let bytes = offset
+            .and_then(|offset| elf.raw.get().get(offset as usize..))
+            .map(|data| data[..data.len().min(ENTRY_BYTES)].to_owned())
+            .unwrap_or_default();
+
+        let mut warnings = Vec::new();
+        if let Some(offset) = offset {
+            if offset >= elf.raw.get().len() as u64 {
+                warnings.push(format!(
+                    "File offset of the entry point (0x{:x}) is past the end of the file",
+                    offset
+                ));
+            }
+        }
+        if e_type != ET_REL {
+            match (&location, &segment) {
+                (_, None) => warnings.push(String::from(
+                    "Entry point is outside of any PT_LOAD segment",
+                )),
+                (Some(location), Some(segment)) => {
+                    if segment.p_flags & PF_X == 0 {
+                        warnings.push(String::from(
+                            "Entry point is in a segment which is not executable",
+                        ));
+                    }
+                    if segment.p_flags & PF_W != 0 {
+                        warnings.push(String::from("Entry point is in a writable segment"));
+                    }
+                    if location.is_bss {
+                        warnings.push(String::from(
+                            "Entry point is in the zero filled part of a segment, which has no bytes in the file",
+                        ));
+                    }
+                }
+                _ => (),
+            }
+            match section.as_deref() {
+                Some(".text") => (),
+                Some(name) => warnings.push(format!(
+                    "Entry point is in section {} instead of .text",
+                    name
+                )),
+                None if elf.sections.0.len() > 1 => {
+                    warnings.push(String::from("Entry point is outside of any section"))
+                }
+                None => (),
+            }
+        }
+
+        Some(Self {
+            address,
+            location,
+            segment,
+            section,
+            symbol,
+            bytes,
+            warnings,
+        })
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Property", "Value"]);
+        let mut push = |property: &str, value: String| {
+            table.push(vec![property.to_owned(), value]);
+        };
+        push("Address", format!("0x{:x}", self.address));
+        let offset = self.location.as_ref().and_then(|location| location.offset);
+        push(
+            "File offset",
+            match offset {
+                Some(offset) => format!("0x{:x}", offset),
+                None => String::from("None"),
+            },
+        );
+        push(
+            "Segment",
+            match &self.segment {
+                Some(segment) => format!(
+                    "{:02} ({} {})",
+                    segment.index,
+                    segment.type_name(),
+                    PFlags(segment.p_flags).to_str()
+                ),
+                None => String::from("None"),
+            },
+        );
+        push(
+            "Section",
+            self.section.clone().unwrap_or_else(|| String::from("None")),
+        );
+        push(
+            "Symbol",
+            self.symbol.clone().unwrap_or_else(|| String::from("None")),
+        );
+        push("Bytes", utils::raw_to_hex(&self.bytes));
+        for warning in &self.warnings {
+            push("Warning", warning.to_owned());
+        }
+        table
+    }
+}
@@ -0,0 +1,155 @@
+use crate::elf::sections::Symbol;
+use crate::elf::{Description, Elf};
+use crate::utils::Table;
+
+// symbol binds
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+const STB_GNU_UNIQUE: u8 = 10;
+
+// symbol visibilities
+const STV_DEFAULT: u8 = 0;
+const STV_PROTECTED: u8 = 3;
+
+/// A symbol the file expects another object to define
+pub struct Import {
+    pub name: String,
+    /// Library the symbol is expected from, named by the version requirement of versioned symbols
+    pub library: Option<String>,
+    pub version: Option<String>,
+    pub sym_type: String,
+    pub is_weak: bool,
+}
+
+/// Undefined dynamic symbols, sorted by library and version
+pub struct Imports(pub Vec<Import>);
+
+impl Imports {
+    pub fn from(elf: &Elf) -> Self {
+        let symbols = elf.sections.dynsym().unwrap_or(&[]);
+        let mut imports: Vec<Import> = symbols
+            .iter()
+            .skip(1)
+            .filter(|symbol| symbol.is_undefined() && !symbol.name_or_empty().is_empty())
+            .map(|symbol| {
+                let version = elf.sections.symbol_version(symbol.index);
+                Import {
+                    name: symbol.name_or_empty().to_owned(),
+                    library: version.as_ref().and_then(|version| version.file.clone()),
+                    version: version.map(|version| version.name),
+                    sym_type: symbol.sym_type().to_str(),
+                    is_weak: symbol.bind().0 == STB_WEAK,
+                }
+            })
+            .collect();
+        // unversioned symbols (no known library) come last
+        imports.sort_by(|a, b| {
+            (a.library.is_none(), &a.library, &a.version, &a.name).cmp(&(
+                b.library.is_none(),
+                &b.library,
+                &b.version,
+                &b.name,
+            ))
+        });
+        Self(imports)
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Library", "Version", "Symbol", "Type", "Bind"]);
+        for import in &self.0 {
+            table.push(vec![
+                import.library.clone().unwrap_or_default(),
+                import.version.clone().unwrap_or_default(),
+                import.name.clone(),
+                import.sym_type.clone(),
+                match import.is_weak {
+                    true => String::from("WEAK"),
+                    false => String::from("GLOBAL"),
+                },
+            ]);
+        }
+        table
+    }
+}
+
+/// A symbol the file makes available to other objects
+pub struct Export {
+    pub name: String,
+    pub version: Option<String>,
+    /// The version is not the default one (`name@VERSION`)
+    pub is_hidden: bool,
+    pub address: u64,
+    pub size: u64,
+    pub sym_type: String,
+    pub bind: String,
+    pub visibility: String,
+}
+
+/// Defined global or weak dynamic symbols with default or protected visibility, sorted by address
+pub struct Exports(pub Vec<Export>);
+
+impl Exports {
+    pub fn from(elf: &Elf) -> Self {
+        let symbols = elf.sections.dynsym().unwrap_or(&[]);
+        let mut exports: Vec<Export> = symbols
+            .iter()
+            .filter(|symbol| Self::is_exported(symbol))
+            .filter_map(|symbol| {
+                let version = elf.sections.symbol_version(symbol.index);
+                let name = symbol.name_or_empty();
+                // every version definition has an absolute symbol of the same name
+                if version.as_ref().map(|version| version.name.as_str()) == Some(name) {
+                    return None;
+                }
+                Some(Export {
+                    name: name.to_owned(),
+                    is_hidden: version.as_ref().is_some_and(|version| version.is_hidden),
+                    version: version.map(|version| version.name),
+                    address: symbol.st_value,
+                    size: symbol.st_size,
+                    sym_type: symbol.sym_type().to_str(),
+                    bind: symbol.bind().to_str(),
+                    visibility: symbol.visibility().to_str(),
+                })
+            })
+            .collect();
+        exports.sort_by(|a, b| (a.address, &a.name).cmp(&(b.address, &b.name)));
+        Self(exports)
+    }
+
+    fn is_exported(symbol: &Symbol) -> bool {
+        !symbol.is_undefined()
+            && !symbol.name_or_empty().is_empty()
+            && matches!(symbol.bind().0, STB_GLOBAL | STB_WEAK | STB_GNU_UNIQUE)
+            && matches!(symbol.visibility().0, STV_DEFAULT | STV_PROTECTED)
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&[
+            "Symbol",
+            "Version",
+            "Address",
+            "Size",
+            "Type",
+            "Bind",
+            "Visibility",
+        ]);
+        for export in &self.0 {
+            let version = match (&export.version, export.is_hidden) {
+                (Some(version), true) => format!("{} (hidden)", version),
+                (Some(version), false) => version.to_owned(),
+                (None, _) => String::new(),
+            };
+            table.push(vec![
+                export.name.clone(),
+                version,
+                format!("0x{:x}", export.address),
+                format!("{}", export.size),
+                export.sym_type.clone(),
+                export.bind.clone(),
+                export.visibility.clone(),
+            ]);
+        }
+        table
+    }
+}
@@ -0,0 +1,409 @@
+use super::Pkcs7Info;
+use crate::elf::{Description, Elf};
+use crate::utils::{self, RcSlice, Table};
+
+const MODULE_SIG_MAGIC: &[u8] = b"~Module signature appended~\n";
+const MODULE_SIG_INFO_SIZE: usize = 12;
+const MODVERSION_INFO_SIZE: usize = 64;
+
+pub struct KernelModule {
+    pub name: Option<String>,
+    pub modinfo: Vec<(String, String)>,
+    pub versions: Vec<ModVersion>,
+    pub exports: Vec<KernelExport>,
+    pub signature: Option<ModuleSignature>,
+}
+
+impl KernelModule {
+    /// Kernel modules are relocatable files with a `.modinfo` or `.gnu.linkonce.this_module` section
+    pub fn is_kernel_module(elf: &Elf) -> bool {
+        elf.hdr.e_type.0 == 1
+            && (elf.sections.by_name(".modinfo").is_some()
+                || elf.sections.by_name(".gnu.linkonce.this_module").is_some())
+    }
+
+    pub fn from(elf: &Elf) -> Option<Self> {
+        if !Self::is_kernel_module(elf) {
+            return None;
+        }
+
+        Some(Self {
+            name: Self::parse_name(elf),
+            modinfo: Self::parse_modinfo(elf),
+            versions: Self::parse_versions(elf),
+            exports: Self::parse_exports(elf),
+            signature: ModuleSignature::from(&elf.raw),
+        })
+    }
+
+    // the module name is stored in `struct module` after `enum module_state state` and `struct list_head list`
+    fn parse_name(elf: &Elf) -> Option<String> {
+        let data = elf.sections.by_name(".gnu.linkonce.this_module")?.data()?;
+        let (offset, len) = match elf.is_64_bit() {
+            true => (24, 56),
+            false => (12, 60),
+        };
+        if data.len() < offset + len {
+            return None;
+        }
+        match utils::raw_to_str(&data[offset..offset + len]).1 {
+            Ok(name) if !name.is_empty() => Some(name.to_owned()),
+            _ => None,
+        }
+    }
+
+    fn parse_modinfo(elf: &Elf) -> Vec<(String, String)> {
+        let mut modinfo = Vec::new();
+        let data = match elf.sections.by_name(".modinfo").and_then(|s| s.data()) {
+            None => return modinfo,
+            Some(data) => data,
+        };
+
+        // .modinfo is a list of NUL terminated "key=value" strings, possibly padded with NULs
+        for entry in data.split(|&c| c == b'\0') {
+            if entry.is_empty() {
+                continue;
+            }
+            let entry = String::from_utf8_lossy(entry);
+            match entry.split_once('=') {
+                Some((key, value)) => modinfo.push((key.to_owned(), value.to_owned())),
+                None => modinfo.push((entry.into_owned(), String::new())),
+            }
+        }
+        modinfo
+    }
+
+    // __versions is an array of `struct modversion_info { unsigned long crc; char name[64 - sizeof(unsigned long)]; }`
+    fn parse_versions(elf: &Elf) -> Vec<ModVersion> {
+        let mut versions = Vec::new();
+        let section = match elf.sections.by_name("__versions") {
+            None => return versions,
+            Some(section) => section,
+        };
+        let data = match section.raw() {
+            None => return versions,
+            Some(data) => data,
+        };
+        let is_little_endian = elf.is_little_endian();
+        let crc_size = match elf.is_64_bit() {
+            true => 8,
+            false => 4,
+        };
+
+        for i in 0..data.get().len() / MODVERSION_INFO_SIZE {
+            let entry = RcSlice::from(
+                data,
+                i * MODVERSION_INFO_SIZE,
+                (i + 1) * MODVERSION_INFO_SIZE,
+            );
+            let crc = match crc_size {
+                8 => entry.read_u64(0, is_little_endian),
+                _ => entry.read_u32(0, is_little_endian) as u64,
+            };
+            let name = &entry.get()[crc_size..];
+            let len = utils::raw_to_str(name).0;
+            let name = String::from_utf8_lossy(&name[..len]).into_owned();
+            versions.push(ModVersion { crc, name });
+        }
+        versions
+    }
+
+    // every exported symbol has a matching `__ksymtab_<name>` symbol in one of the __ksymtab* sections
+    fn parse_exports(elf: &Elf) -> Vec<KernelExport> {
+        let mut exports = Vec::new();
+        for symbol in elf.sections.symbols() {
+            let name = match symbol
+                .name
+                .as_ref()
+                .and_then(|n| n.strip_prefix("__ksymtab_"))
+            {
+                None => continue,
+                Some(name) => name,
+            };
+            let section = match elf.sections.0.get(symbol.st_shndx.0 as usize) {
+                None => continue,
+                Some(section) => section,
+            };
+            let section_name = match &section.name {
+                Some(section_name)
+                    if section_name.trim_start_matches('_').starts_with("ksymtab") =>
+                {
+                    section_name
+                }
+                _ => continue,
+            };
+            exports.push(KernelExport {
+                name: name.to_owned(),
+                section: section_name.to_owned(),
+                gpl_only: section_name.contains("gpl"),
+            });
+        }
+        exports
+    }
+
+    pub fn modinfo_values(&self, key: &str) -> Vec<&str> {
+        self.modinfo
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| &v[..])
+            .collect()
+    }
+
+    pub fn modinfo_value(&self, key: &str) -> Option<&str> {
+        self.modinfo_values(key).first().copied()
+    }
+
+    pub fn vermagic(&self) -> Option<&str> {
+        self.modinfo_value("vermagic")
+    }
+
+    pub fn depends(&self) -> Vec<&str> {
+        match self.modinfo_value("depends") {
+            None => Vec::new(),
+            Some(depends) => depends.split(',').filter(|d| !d.is_empty()).collect(),
+        }
+    }
+
+    pub fn summary_table(&self) -> Table {
+        let mut table = Table::new(&["Property", "Value"]);
+        let na = || "N/A".to_owned();
+        table.push(vec![
+            "Name".to_owned(),
+            self.name.clone().unwrap_or_else(na),
+        ]);
+        for key in [
+            "license",
+            "vermagic",
+            "srcversion",
+            "version",
+            "description",
+            "author",
+        ] {
+            if let Some(value) = self.modinfo_value(key) {
+                table.push(vec![key.to_owned(), value.to_owned()]);
+            }
+        }
+        table.push(vec!["depends".to_owned(), self.depends().join(", ")]);
+        table.push(vec![
+            "Aliases".to_owned(),
+            format!("{}", self.modinfo_values("alias").len()),
+        ]);
+        table.push(vec![
+            "Parameters".to_owned(),
+            format!("{}", self.modinfo_values("parm").len()),
+        ]);
+        table.push(vec![
+            "Symbol versions".to_owned(),
+            format!("{}", self.versions.len()),
+        ]);
+        table.push(vec![
+            "Exported symbols".to_owned(),
+            format!("{}", self.exports.len()),
+        ]);
+        table.push(vec![
+            "Signed".to_owned(),
+            match self.signature {
+                Some(_) => "Yes".to_owned(),
+                None => "No".to_owned(),
+            },
+        ]);
+        table
+    }
+
+    pub fn modinfo_table(&self) -> Table {
+        let mut table = Table::new(&["Key", "Value"]);
+        for (key, value) in &self.modinfo {
+            table.push(vec![key.to_owned(), value.to_owned()]);
+        }
+        table
+    }
+
+    pub fn versions_table(&self) -> Table {
+        let mut table = Table::new(&["CRC", "Symbol"]);
+        for version in &self.versions {
+            table.push(vec![
+                format!("0x{:08x}", version.crc),
+                version.name.to_owned(),
+            ]);
+        }
+        table
+    }
+
+    pub fn exports_table(&self) -> Table {
+        let mut table = Table::new(&["Symbol", "Section", "License"]);
+        for export in &self.exports {
+            table.push(vec![
+                export.name.to_owned(),
+                export.section.to_owned(),
+                match export.gpl_only {
+                    true => "GPL only".to_owned(),
+                    false => "Any".to_owned(),
+                },
+            ]);
+        }
+        table
+    }
+}
+
+pub struct ModVersion {
+    pub crc: u64,
+    pub name: String,
+}
+
+pub struct KernelExport {
+    pub name: String,
+    pub section: String,
+    pub gpl_only: bool,
+}
+
+/// Signature appended to the module by the kernel's `sign-file` tool.
+///
+/// The layout of the trailer is `[signer][key id][signature][struct module_signature][magic]`.
+pub struct ModuleSignature {
+    pub offset: usize,
+    pub algo: SigAlgo,
+    pub hash: SigHash,
+    pub id_type: SigIdType,
+    pub signer: String,
+    pub key_id: Vec<u8>,
+    pub sig_offset: usize,
+    pub sig_len: usize,
+    pub pkcs7: Option<Pkcs7Info>,
+}
+
+impl ModuleSignature {
+    pub fn from(raw: &RcSlice<u8>) -> Option<Self> {
+        let data = raw.get();
+        if !data.ends_with(MODULE_SIG_MAGIC) {
+            return None;
+        }
+        let info_offset = data
+            .len()
+            .checked_sub(MODULE_SIG_MAGIC.len() + MODULE_SIG_INFO_SIZE)?;
+        let info = &data[info_offset..info_offset + MODULE_SIG_INFO_SIZE];
+        let signer_len = info[3] as usize;
+        let key_id_len = info[4] as usize;
+        // sig_len is always big endian
+        let sig_len = raw.read_u32(info_offset + 8, false) as usize;
+
+        let sig_offset = info_offset.checked_sub(sig_len)?;
+        let key_id_offset = sig_offset.checked_sub(key_id_len)?;
+        let offset = key_id_offset.checked_sub(signer_len)?;
+
+        let id_type = SigIdType(info[2]);
+        let signature = &data[sig_offset..info_offset];
+        let pkcs7 = match id_type.0 {
+            2 => Pkcs7Info::from(signature),
+            _ => None,
+        };
+
+        Some(Self {
+            offset,
+            algo: SigAlgo(info[0]),
+            hash: SigHash(info[1]),
+            id_type,
+            signer: String::from_utf8_lossy(&data[offset..key_id_offset]).into_owned(),
+            key_id: data[key_id_offset..sig_offset].to_owned(),
+            sig_offset,
+            sig_len,
+            pkcs7,
+        })
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Property", "Value"]);
+        table.push(vec![
+            "Trailer offset".to_owned(),
+            format!("0x{:x}", self.offset),
+        ]);
+        table.push(vec!["ID type".to_owned(), self.id_type.to_str()]);
+        // algo, hash, signer and key id are only meaningful for the legacy formats
+        if self.id_type.0 != 2 {
+            table.push(vec!["Algorithm".to_owned(), self.algo.to_str()]);
+            table.push(vec!["Hash".to_owned(), self.hash.to_str()]);
+            table.push(vec!["Signer".to_owned(), self.signer.to_owned()]);
+            table.push(vec!["Key ID".to_owned(), utils::raw_to_hex(&self.key_id)]);
+        }
+        table.push(vec![
+            "Signature offset".to_owned(),
+            format!("0x{:x}", self.sig_offset),
+        ]);
+        table.push(vec![
+            "Signature size".to_owned(),
+            format!("{} (bytes)", self.sig_len),
+        ]);
+        if let Some(pkcs7) = &self.pkcs7 {
+            let na = || "N/A".to_owned();
+            table.push(vec![
+                "Content type".to_owned(),
+                pkcs7.content_type.clone().unwrap_or_else(na),
+            ]);
+            table.push(vec![
+                "Digest algorithm".to_owned(),
+                pkcs7.digest_algorithm.clone().unwrap_or_else(na),
+            ]);
+            table.push(vec![
+                "Signature algorithm".to_owned(),
+                pkcs7.signature_algorithm.clone().unwrap_or_else(na),
+            ]);
+            let issuer = pkcs7
+                .issuer
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<String>>()
+                .join(", ");
+            table.push(vec!["Issuer".to_owned(), issuer]);
+            table.push(vec![
+                "Serial number".to_owned(),
+                match &pkcs7.serial {
+                    Some(serial) => utils::raw_to_hex(serial),
+                    None => na(),
+                },
+            ]);
+        }
+        table
+    }
+}
+
+pub struct SigAlgo(pub u8);
+
+impl Description for SigAlgo {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "DSA".to_owned(),
+            1 => "RSA".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct SigHash(pub u8);
+
+impl Description for SigHash {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "MD4".to_owned(),
+            1 => "MD5".to_owned(),
+            2 => "SHA1".to_owned(),
+            3 => "RIPEMD-160".to_owned(),
+            4 => "SHA256".to_owned(),
+            5 => "SHA384".to_owned(),
+            6 => "SHA512".to_owned(),
+            7 => "SHA224".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct SigIdType(pub u8);
+
+impl Description for SigIdType {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "PGP".to_owned(),
+            1 => "X.509".to_owned(),
+            2 => "PKCS#7".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::elf::Elf;
+use crate::utils::Table;
+
+/// A structure of the file which occupies a range of bytes
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayoutItem {
+    ElfHeader,
+    ProgramHeaders,
+    SectionHeaders,
+    Section(usize),
+}
+
+pub enum RegionKind {
+    /// Bytes covered by exactly one structure
+    Claimed,
+    /// Bytes covered by more than one structure
+    Overlap,
+    /// Bytes between structures which nothing refers to (may still be part of a segment, e.g. alignment padding)
+    Gap,
+    /// Bytes after the last structure of the file (overlays, appended signatures, payloads)
+    TrailingData,
+}
+
+pub struct Region {
+    pub start: u64,
+    pub end: u64,
+    pub kind: RegionKind,
+    pub items: Vec<LayoutItem>,
+    /// Indexes of the program headers whose file image contains the region
+    pub segments: Vec<usize>,
+}
+
+/// Map of what covers every byte range of the file
+pub struct FileLayout {
+    pub file_size: u64,
+    pub regions: Vec<Region>,
+}
+
+impl FileLayout {
+    pub fn from(elf: &Elf) -> Self {
+        let file_size = elf.raw.get().len() as u64;
+        let hdr = &elf.hdr;
+
+        // collect the byte ranges of every structure, clamped to the file
+        let mut ranges = Vec::new();
+        let mut push = |item: LayoutItem, start: u64, size: u64| {
+            let end = start.saturating_add(size).min(file_size);
+            if size != 0 && start < end {
+                ranges.push((item, start, end));
+            }
+        };
+        push(LayoutItem::ElfHeader, 0, hdr.e_ehsize as u64);
+        push(
+            LayoutItem::ProgramHeaders,
+            hdr.e_phoff.to_u64(),
+            hdr.e_phnum as u64 * hdr.e_phentsize as u64,
+        );
+        push(
+            LayoutItem::SectionHeaders,
+            hdr.e_shoff.to_u64(),
+            hdr.e_shnum as u64 * hdr.e_shentsize as u64,
+        );
+        for section in elf.sections.0.iter().skip(1) {
+            if !section.is_nobits() {
+                push(
+                    LayoutItem::Section(section.index),
+                    section.file_offset as u64,
+                    section.size as u64,
+                );
+            }
+        }
+        let segments: Vec<(usize, u64, u64)> = elf
+            .phdr_table
+            .segments()
+            .iter()
+            .filter(|segment| segment.filesz != 0)
+            .map(|segment| {
+                let end = segment.offset.saturating_add(segment.filesz).min(file_size);
+                (segment.index, segment.offset, end)
+            })
+            .collect();
+
+        // split the file at every boundary of a structure or segment
+        let mut bounds = vec![0, file_size];
+        for (_, start, end) in &ranges {
+            bounds.push(*start);
+            bounds.push(*end);
+        }
+        for (_, start, end) in &segments {
+            bounds.push(*start);
+            bounds.push(*end);
+        }
+        bounds.retain(|bound| *bound <= file_size);
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let last_end = ranges
+            .iter()
+            .map(|(_, _, end)| *end)
+            .chain(segments.iter().map(|(_, _, end)| *end))
+            .max()
+            .unwrap_or(0);
+
+        let mut regions: Vec<Region> = Vec::new();
+        for window in bounds.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            let items: Vec<LayoutItem> = ranges
+                .iter()
+                .filter(|(_, s, e)| *s <= start && end <= *e)
+                .map(|(item, _, _)| *item)
+                .collect();
+            let covering: Vec<usize> = segments
+                .iter()
+                .filter(|(_, s, e)| *s <= start && end <= *e)
+                .map(|(index, _, _)| *index)
+                .collect();
+            let kind = match items.len() {
+                0 if start >= last_end => RegionKind::TrailingData,
+                0 => RegionKind::Gap,
+                1 => RegionKind::Claimed,
+                _ => RegionKind::Overlap,
+            };
+
+            // merge with the previous region if it is covered by exactly the same things
+            if let Some(previous) = regions.last_mut() {
+                if previous.items == items && previous.segments == covering && previous.end == start
+                {
+                    previous.end = end;
+                    continue;
+                }
+            }
+            regions.push(Region {
+                start,
+                end,
+                kind,
+                items,
+                segments: covering,
+            });
+        }
+        Self { file_size, regions }
+    }
+
+    pub fn gaps(&self) -> impl Iterator<Item = &Region> {
+        self.regions
+            .iter()
+            .filter(|region| matches!(region.kind, RegionKind::Gap))
+    }
+
+    pub fn overlaps(&self) -> impl Iterator<Item = &Region> {
+        self.regions
+            .iter()
+            .filter(|region| matches!(region.kind, RegionKind::Overlap))
+    }
+
+    /// Data appended after the last structure of the file
+    pub fn trailing_data(&self) -> Option<&Region> {
+        self.regions
+            .iter()
+            .find(|region| matches!(region.kind, RegionKind::TrailingData))
+    }
+
+    pub fn to_table(&self, elf: &Elf) -> Table {
+        let mut table = Table::new(&["Start", "End", "Size", "Contents", "Segments", "Note"]);
+        for region in &self.regions {
+            let contents: Vec<String> = region
+                .items
+                .iter()
+                .map(|item| match item {
+                    LayoutItem::ElfHeader => String::from("ELF header"),
+                    LayoutItem::ProgramHeaders => String::from("Program header table"),
+                    LayoutItem::SectionHeaders => String::from("Section header table"),
+                    LayoutItem::Section(index) => match elf.sections.0[*index].name.as_deref() {
+                        Some(name) if !name.is_empty() => name.to_owned(),
+                        _ => format!("Section {}", index),
+                    },
+                })
+                .collect();
+            let segments: Vec<String> = region
+                .segments
+                .iter()
+                .map(|index| format!("{:02}", index))
+                .collect();
+            let note = match region.kind {
+                RegionKind::Claimed => "",
+                RegionKind::Overlap => "Overlap",
+                RegionKind::Gap if region.segments.is_empty() => "Gap",
+                RegionKind::Gap => "Gap (inside segment)",
+                RegionKind::TrailingData => "Trailing data",
+            };
+            table.push(vec![
+                format!("0x{:x}", region.start),
+                format!("0x{:x}", region.end),
+                format!("0x{:x}", region.end - region.start),
+                contents.join(", "),
+                segments.join(" "),
+                note.to_owned(),
+            ]);
+        }
+        table
+    }
+}
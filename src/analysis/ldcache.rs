@@ -0,0 +1,341 @@
+use crate::elf::{Description, Elf, ParsingError};
+use crate::utils::{self, RcSlice, Table};
+use std::rc::Rc;
+
+const OLD_MAGIC: &[u8] = b"ld.so-1.7.0";
+const NEW_MAGIC: &[u8] = b"glibc-ld.so.cache1.1";
+const OLD_HEADER_SIZE: usize = 16;
+const OLD_ENTRY_SIZE: usize = 12;
+const NEW_HEADER_SIZE: usize = 48;
+const NEW_ENTRY_SIZE: usize = 24;
+
+// byte order recorded in the flags of the new format
+const ENDIAN_MASK: u8 = 0x3;
+const ENDIAN_LITTLE: u8 = 2;
+const ENDIAN_BIG: u8 = 3;
+
+// extensions of the new format (glibc 2.33 and later)
+const EXTENSION_MAGIC: u32 = 0xeaa42174;
+const EXTENSION_TAG_GENERATOR: u32 = 0;
+const EXTENSION_TAG_GLIBC_HWCAPS: u32 = 1;
+/// The low 32 bits of hwcap index the `glibc-hwcaps` subdirectory names instead of being a bitmask
+const HWCAP_EXTENSION: u64 = 1 << 62;
+
+// library flags
+const FLAG_TYPE_MASK: u32 = 0x00ff;
+const FLAG_ELF_LIBC6: u32 = 0x0003;
+const FLAG_REQUIRED_MASK: u32 = 0xff00;
+
+pub enum LdCacheFormat {
+    /// `ld.so-1.7.0` only
+    Old,
+    /// `glibc-ld.so.cache1.1` only
+    New,
+    /// The old format followed by the new one, for compatibility with old loaders
+    Combined,
+}
+
+/// A library known to the dynamic loader cache
+pub struct LdCacheEntry {
+    pub flags: LdCacheFlags,
+    /// The soname
+    pub key: String,
+    /// Path of the library
+    pub value: String,
+    /// Minimum kernel version, 0 if the library doesn't require one
+    pub osversion: u32,
+    pub hwcap: u64,
+    /// The `glibc-hwcaps` subdirectory the library was found in
+    pub hwcaps_subdirectory: Option<String>,
+}
+
+/// Dynamic loader cache (`/etc/ld.so.cache`), mapping sonames to library paths
+pub struct LdCache {
+    pub format: LdCacheFormat,
+    pub is_little_endian: bool,
+    pub entries: Vec<LdCacheEntry>,
+    /// Name of the program which created the cache
+    pub generator: Option<String>,
+}
+
+impl LdCache {
+    pub fn from(raw: Vec<u8>) -> Result<Self, ParsingError> {
+        let len = raw.len();
+        let data = RcSlice::new(Rc::new(raw), 0, len);
+
+        if data.get().starts_with(NEW_MAGIC) {
+            let mut cache = Self::from_new(&data)?;
+            cache.format = LdCacheFormat::New;
+            return Ok(cache);
+        }
+        if !data.get().starts_with(OLD_MAGIC) || len < OLD_HEADER_SIZE {
+            return Err(ParsingError::InvalidMagicBytes(String::from(
+                "Not a dynamic loader cache (no ld.so-1.7.0 or glibc-ld.so.cache1.1 magic)",
+            )));
+        }
+
+        // the old format has no byte order marker, so pick the one which gives a plausible entry count
+        let is_little_endian = (data.read_u32(12, true) as usize)
+            .checked_mul(OLD_ENTRY_SIZE)
+            .is_some_and(|size| OLD_HEADER_SIZE + size <= len);
+        let nlibs = data.read_u32(12, is_little_endian) as usize;
+        let strings = OLD_HEADER_SIZE + nlibs.saturating_mul(OLD_ENTRY_SIZE);
+        if strings > len {
+            return Err(ParsingError::InvalidNativeSize(String::from(
+                "The number of libraries in the cache header exceeds the size of the file",
+            )));
+        }
+
+        // a new format cache may follow the old entries, aligned to 8 bytes
+        let new_start = (strings + 7) & !7;
+        if len > new_start && data.get()[new_start..].starts_with(NEW_MAGIC) {
+            let mut cache = Self::from_new(&RcSlice::from(&data, new_start, len))?;
+            cache.format = LdCacheFormat::Combined;
+            return Ok(cache);
+        }
+
+        let string_table = RcSlice::from(&data, strings, len);
+        let entries = (0..nlibs)
+            .map(|i| {
+                let offset = OLD_HEADER_SIZE + i * OLD_ENTRY_SIZE;
+                let entry = RcSlice::from(&data, offset, offset + OLD_ENTRY_SIZE);
+                LdCacheEntry {
+                    flags: LdCacheFlags(entry.read_u32(0, is_little_endian)),
+                    key: read_string(&string_table, entry.read_u32(4, is_little_endian)),
+                    value: read_string(&string_table, entry.read_u32(8, is_little_endian)),
+                    osversion: 0,
+                    hwcap: 0,
+                    hwcaps_subdirectory: None,
+                }
+            })
+            .collect();
+        Ok(Self {
+            format: LdCacheFormat::Old,
+            is_little_endian,
+            entries,
+            generator: None,
+        })
+    }
+
+    /// Decode the new format, whose string offsets are relative to its own header
+    fn from_new(data: &RcSlice<u8>) -> Result<Self, ParsingError> {
+        let len = data.get().len();
+        if len < NEW_HEADER_SIZE {
+            return Err(ParsingError::InvalidNativeSize(String::from(
+                "The cache is too small for its header",
+            )));
+        }
+        let is_little_endian = match data.get()[28] & ENDIAN_MASK {
+            ENDIAN_LITTLE => true,
+            ENDIAN_BIG => false,
+            // caches written before glibc 2.32 don't record the byte order
+            _ => (data.read_u32(20, true) as usize)
+                .checked_mul(NEW_ENTRY_SIZE)
+                .is_some_and(|size| NEW_HEADER_SIZE + size <= len),
+        };
+        let nlibs = data.read_u32(20, is_little_endian) as usize;
+        let extension_offset = data.read_u32(32, is_little_endian) as usize;
+        if NEW_HEADER_SIZE + nlibs.saturating_mul(NEW_ENTRY_SIZE) > len {
+            return Err(ParsingError::InvalidNativeSize(String::from(
+                "The number of libraries in the cache header exceeds the size of the file",
+            )));
+        }
+
+        // extensions hold the generator and the names of the glibc-hwcaps subdirectories
+        let mut generator = None;
+        let mut hwcaps = Vec::new();
+        if extension_offset != 0 && extension_offset + 8 <= len {
+            let le = is_little_endian;
+            if data.read_u32(extension_offset, le) == EXTENSION_MAGIC {
+                let count = data.read_u32(extension_offset + 4, le) as usize;
+                for i in 0..count {
+                    let section = extension_offset + 8 + i * 16;
+                    if section + 16 > len {
+                        break;
+                    }
+                    let tag = data.read_u32(section, le);
+                    let offset = data.read_u32(section + 8, le) as usize;
+                    let size = data.read_u32(section + 12, le) as usize;
+                    if offset.saturating_add(size) > len {
+                        continue;
+                    }
+                    match tag {
+                        EXTENSION_TAG_GENERATOR => {
+                            let raw = &data.get()[offset..offset + size];
+                            generator = Some(String::from_utf8_lossy(raw).into_owned());
+                        }
+                        EXTENSION_TAG_GLIBC_HWCAPS => {
+                            hwcaps = (0..size / 4)
+                                .map(|j| read_string(data, data.read_u32(offset + j * 4, le)))
+                                .collect();
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        let entries = (0..nlibs)
+            .map(|i| {
+                let offset = NEW_HEADER_SIZE + i * NEW_ENTRY_SIZE;
+                let entry = RcSlice::from(data, offset, offset + NEW_ENTRY_SIZE);
+                let hwcap = entry.read_u64(16, is_little_endian);
+                let hwcaps_subdirectory = match hwcap & HWCAP_EXTENSION != 0 {
+                    true => hwcaps.get(hwcap as u32 as usize).cloned(),
+                    false => None,
+                };
+                LdCacheEntry {
+                    flags: LdCacheFlags(entry.read_u32(0, is_little_endian)),
+                    key: read_string(data, entry.read_u32(4, is_little_endian)),
+                    value: read_string(data, entry.read_u32(8, is_little_endian)),
+                    osversion: entry.read_u32(12, is_little_endian),
+                    hwcap,
+                    hwcaps_subdirectory,
+                }
+            })
+            .collect();
+        Ok(Self {
+            format: LdCacheFormat::New,
+            is_little_endian,
+            entries,
+            generator,
+        })
+    }
+
+    /// Find the library the loader would use for a soname needed by `elf`.
+    /// The CPU is unknown, so entries of the baseline ABI are preferred over hwcaps specific ones
+    pub fn lookup(&self, soname: &str, elf: &Elf) -> Option<&LdCacheEntry> {
+        let mut candidates = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key == soname && entry.flags.is_compatible(elf));
+        let first = candidates.next()?;
+        match first.hwcap == 0 {
+            true => Some(first),
+            false => candidates.find(|entry| entry.hwcap == 0).or(Some(first)),
+        }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Soname", "Flags", "Hwcaps", "OS Version", "Path"]);
+        for entry in &self.entries {
+            let hwcap = match (&entry.hwcaps_subdirectory, entry.hwcap) {
+                (Some(subdirectory), _) => subdirectory.to_owned(),
+                (None, 0) => String::new(),
+                (None, hwcap) => format!("0x{:016x}", hwcap),
+            };
+            let osversion = match entry.osversion {
+                0 => String::new(),
+                version => format!(
+                    "{}.{}.{}",
+                    (version >> 16) & 0xff,
+                    (version >> 8) & 0xff,
+                    version & 0xff
+                ),
+            };
+            table.push(vec![
+                entry.key.clone(),
+                entry.flags.to_str(),
+                hwcap,
+                osversion,
+                entry.value.clone(),
+            ]);
+        }
+        table
+    }
+}
+
+fn read_string(data: &RcSlice<u8>, offset: u32) -> String {
+    match data.get().get(offset as usize..) {
+        Some(raw) => String::from_utf8_lossy(&raw[..utils::raw_to_str(raw).0]).into_owned(),
+        None => String::new(),
+    }
+}
+
+/// Library type and ABI of a cache entry, rendered the way `ldconfig -p` prints them
+pub struct LdCacheFlags(pub u32);
+
+impl LdCacheFlags {
+    /// Whether a library with these flags can be loaded by `elf`, following `_dl_cache_check_flags`
+    pub fn is_compatible(&self, elf: &Elf) -> bool {
+        if self.0 & FLAG_TYPE_MASK != FLAG_ELF_LIBC6 {
+            return false;
+        }
+        let required = self.0 & FLAG_REQUIRED_MASK;
+        let e_flags = elf.hdr.e_flags.0;
+        let expected = match (elf.hdr.e_machine.0, elf.is_64_bit()) {
+            // x86-64, x32
+            (62, true) => 0x0300,
+            (62, false) => 0x0800,
+            // SPARC v9, IA-64, S/390, PowerPC
+            (43, _) => 0x0100,
+            (50, _) => 0x0200,
+            (22, true) => 0x0400,
+            (21, _) => 0x0500,
+            // AArch64
+            (183, _) => 0x0a00,
+            // ARM, hard or soft float
+            (40, _) => match e_flags & 0x400 != 0 {
+                true => 0x0900,
+                false => 0x0b00,
+            },
+            // MIPS, by ABI and NaN encoding
+            (8, _) => {
+                let nan2008 = e_flags & 0x400 != 0;
+                let n32 = e_flags & 0x20 != 0;
+                match (elf.is_64_bit(), n32, nan2008) {
+                    (true, _, false) => 0x0700,
+                    (true, _, true) => 0x0e00,
+                    (false, true, false) => 0x0600,
+                    (false, true, true) => 0x0d00,
+                    (false, false, false) => 0,
+                    (false, false, true) => 0x0c00,
+                }
+            }
+            // RISC-V, by float ABI
+            (243, _) => match e_flags & 0x6 {
+                0 => 0x0f00,
+                4 => 0x1000,
+                _ => required,
+            },
+            // LoongArch, by float ABI
+            (258, _) => match e_flags & 0x3 {
+                1 => 0x1100,
+                3 => 0x1200,
+                _ => required,
+            },
+            _ => 0,
+        };
+        // ARM libraries without a float ABI in the cache work with either
+        required == expected || (elf.hdr.e_machine.0 == 40 && required == 0)
+    }
+}
+
+impl Description for LdCacheFlags {
+    fn to_str(&self) -> String {
+        let kind = match self.0 & FLAG_TYPE_MASK {
+            0x0000 => "libc4".to_owned(),
+            0x0001 => "ELF".to_owned(),
+            0x0002 => "libc5".to_owned(),
+            0x0003 => "libc6".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        };
+        let required = match (self.0 & FLAG_REQUIRED_MASK) >> 8 {
+            0x00 => "",
+            0x01 | 0x04 | 0x05 | 0x07 => ",64bit",
+            0x02 => ",IA-64",
+            0x03 => ",x86-64",
+            0x06 => ",N32",
+            0x08 => ",x32",
+            0x09 => ",hard-float",
+            0x0a => ",AArch64",
+            0x0b | 0x0f | 0x11 => ",soft-float",
+            0x0c => ",nan2008",
+            0x0d => ",N32,nan2008",
+            0x0e => ",64bit,nan2008",
+            0x10 | 0x12 => ",double-float",
+            other => return format!("{},<unknown: 0x{:x}>", kind, other),
+        };
+        format!("{}{}", kind, required)
+    }
+}
@@ -0,0 +1,359 @@
+use super::parse_version;
+use crate::elf::Elf;
+use crate::utils::Table;
+
+/// Libraries manylinux1 (PEP 513) allows an extension to link against without bundling them
+const MANYLINUX1_LIBRARIES: &[&str] = &[
+    "libpanelw.so.5",
+    "libncursesw.so.5",
+    "libcrypt.so.1",
+    "libgcc_s.so.1",
+    "libstdc++.so.6",
+    "libm.so.6",
+    "libdl.so.2",
+    "librt.so.1",
+    "libc.so.6",
+    "libnsl.so.1",
+    "libutil.so.1",
+    "libpthread.so.0",
+    "libresolv.so.2",
+    "libX11.so.6",
+    "libXext.so.6",
+    "libXrender.so.1",
+    "libICE.so.6",
+    "libSM.so.6",
+    "libGL.so.1",
+    "libgobject-2.0.so.0",
+    "libgthread-2.0.so.0",
+    "libglib-2.0.so.0",
+];
+
+/// manylinux2010 (PEP 571) drops the ncurses libraries
+const MANYLINUX2010_LIBRARIES: &[&str] = &[
+    "libcrypt.so.1",
+    "libgcc_s.so.1",
+    "libstdc++.so.6",
+    "libm.so.6",
+    "libdl.so.2",
+    "librt.so.1",
+    "libc.so.6",
+    "libnsl.so.1",
+    "libutil.so.1",
+    "libpthread.so.0",
+    "libresolv.so.2",
+    "libX11.so.6",
+    "libXext.so.6",
+    "libXrender.so.1",
+    "libICE.so.6",
+    "libSM.so.6",
+    "libGL.so.1",
+    "libgobject-2.0.so.0",
+    "libgthread-2.0.so.0",
+    "libglib-2.0.so.0",
+];
+
+/// manylinux2014 (PEP 599) and later policies also drop libcrypt
+const MANYLINUX2014_LIBRARIES: &[&str] = &[
+    "libgcc_s.so.1",
+    "libstdc++.so.6",
+    "libm.so.6",
+    "libdl.so.2",
+    "librt.so.1",
+    "libc.so.6",
+    "libnsl.so.1",
+    "libutil.so.1",
+    "libpthread.so.0",
+    "libresolv.so.2",
+    "libX11.so.6",
+    "libXext.so.6",
+    "libXrender.so.1",
+    "libICE.so.6",
+    "libSM.so.6",
+    "libGL.so.1",
+    "libgobject-2.0.so.0",
+    "libgthread-2.0.so.0",
+    "libglib-2.0.so.0",
+];
+
+// machines
+const EM_386: u16 = 3;
+const EM_PPC64: u16 = 21;
+const EM_S390: u16 = 22;
+const EM_ARM: u16 = 40;
+const EM_X86_64: u16 = 62;
+const EM_AARCH64: u16 = 183;
+
+/// A manylinux platform policy, as used by auditwheel
+pub struct ManylinuxPolicy {
+    pub name: &'static str,
+    /// Legacy name of the policy (PEP 513, PEP 571, PEP 599)
+    pub alias: Option<&'static str>,
+    /// Highest version allowed for each version family
+    pub max_versions: &'static [(&'static str, &'static str)],
+    pub libraries: &'static [&'static str],
+    /// Machines the policy is defined for
+    pub machines: &'static [u16],
+}
+
+/// Known policies, from the oldest (most compatible) to the newest
+pub const MANYLINUX_POLICIES: &[ManylinuxPolicy] = &[
+    ManylinuxPolicy {
+        name: "manylinux_2_5",
+        alias: Some("manylinux1"),
+        max_versions: &[
+            ("GLIBC_", "2.5"),
+            ("CXXABI_", "1.3.1"),
+            ("GLIBCXX_", "3.4.8"),
+            ("GCC_", "4.2.0"),
+        ],
+        libraries: MANYLINUX1_LIBRARIES,
+        machines: &[EM_386, EM_X86_64],
+    },
+    ManylinuxPolicy {
+        name: "manylinux_2_12",
+        alias: Some("manylinux2010"),
+        max_versions: &[
+            ("GLIBC_", "2.12"),
+            ("CXXABI_", "1.3.3"),
+            ("GLIBCXX_", "3.4.13"),
+            ("GCC_", "4.5.0"),
+        ],
+        libraries: MANYLINUX2010_LIBRARIES,
+        machines: &[EM_386, EM_X86_64],
+    },
+    ManylinuxPolicy {
+        name: "manylinux_2_17",
+        alias: Some("manylinux2014"),
+        max_versions: &[
+            ("GLIBC_", "2.17"),
+            ("CXXABI_", "1.3.7"),
+            ("GLIBCXX_", "3.4.19"),
+            ("GCC_", "4.8.0"),
+        ],
+        libraries: MANYLINUX2014_LIBRARIES,
+        machines: &[EM_386, EM_X86_64, EM_AARCH64, EM_PPC64, EM_S390, EM_ARM],
+    },
+    ManylinuxPolicy {
+        name: "manylinux_2_24",
+        alias: None,
+        max_versions: &[
+            ("GLIBC_", "2.24"),
+            ("CXXABI_", "1.3.10"),
+            ("GLIBCXX_", "3.4.22"),
+            ("GCC_", "6.0.0"),
+        ],
+        libraries: MANYLINUX2014_LIBRARIES,
+        machines: &[EM_386, EM_X86_64, EM_AARCH64, EM_PPC64, EM_S390, EM_ARM],
+    },
+    ManylinuxPolicy {
+        name: "manylinux_2_28",
+        alias: None,
+        max_versions: &[
+            ("GLIBC_", "2.28"),
+            ("CXXABI_", "1.3.11"),
+            ("GLIBCXX_", "3.4.25"),
+            ("GCC_", "7.0.0"),
+        ],
+        libraries: MANYLINUX2014_LIBRARIES,
+        machines: &[EM_X86_64, EM_AARCH64, EM_PPC64, EM_S390, EM_ARM],
+    },
+];
+
+impl ManylinuxPolicy {
+    pub fn display_name(&self) -> String {
+        match self.alias {
+            Some(alias) => format!("{} ({})", self.name, alias),
+            None => self.name.to_owned(),
+        }
+    }
+
+    /// Whether a library may be linked against. The dynamic loader is always allowed, libpython never is
+    pub fn allows_library(&self, name: &str) -> bool {
+        name.starts_with("ld-linux")
+            || name.starts_with("ld64.so")
+            || self.libraries.contains(&name)
+    }
+}
+
+pub enum ViolationKind {
+    /// A DT_NEEDED library which is not part of the policy
+    Library,
+    /// A symbol which requires a newer version than the policy allows
+    SymbolVersion,
+}
+
+pub struct PolicyViolation {
+    pub kind: ViolationKind,
+    /// Library or symbol name
+    pub subject: String,
+    pub detail: String,
+}
+
+/// Result of checking a file against a manylinux policy
+pub struct PolicyCheck {
+    pub policy: &'static ManylinuxPolicy,
+    /// The policy is defined for the machine of the file
+    pub is_applicable: bool,
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl PolicyCheck {
+    pub fn from(elf: &Elf, policy: &'static ManylinuxPolicy) -> Self {
+        let mut violations = Vec::new();
+        let needed = match elf.sections.dynamic() {
+            Some(dynamic) => dynamic.needed(),
+            None => Vec::new(),
+        };
+        for library in needed {
+            if library.starts_with("libpython") {
+                violations.push(PolicyViolation {
+                    kind: ViolationKind::Library,
+                    subject: library.to_owned(),
+                    detail: String::from("Extension modules must not link against libpython"),
+                });
+            } else if !policy.allows_library(library) {
+                violations.push(PolicyViolation {
+                    kind: ViolationKind::Library,
+                    subject: library.to_owned(),
+                    detail: String::from(
+                        "Not allowed by the policy, it has to be bundled with the wheel",
+                    ),
+                });
+            }
+        }
+
+        for symbol in elf.sections.dynsym().unwrap_or(&[]) {
+            if !symbol.is_undefined() {
+                continue;
+            }
+            let version = match elf.sections.symbol_version(symbol.index) {
+                Some(version) => version.name,
+                None => continue,
+            };
+            let (family, max) = match policy
+                .max_versions
+                .iter()
+                .find(|(family, _)| version.starts_with(family))
+            {
+                Some(found) => *found,
+                None => continue,
+            };
+            let required = version.strip_prefix(family).and_then(parse_version);
+            if let (Some(required), Some(allowed)) = (required, parse_version(max)) {
+                if required > allowed {
+                    violations.push(PolicyViolation {
+                        kind: ViolationKind::SymbolVersion,
+                        subject: symbol.name_or_empty().to_owned(),
+                        detail: format!(
+                            "Requires {}, the policy allows up to {}{}",
+                            version, family, max
+                        ),
+                    });
+                }
+            }
+        }
+
+        Self {
+            policy,
+            is_applicable: policy.machines.contains(&elf.hdr.e_machine.0),
+            violations,
+        }
+    }
+
+    pub fn is_compliant(&self) -> bool {
+        self.is_applicable && self.violations.is_empty()
+    }
+}
+
+/// Python extension module checks: the module initialization functions and compliance with each manylinux policy
+pub struct ManylinuxReport {
+    /// Exported `PyInit_*` functions, one per module the file implements
+    pub init_functions: Vec<String>,
+    pub checks: Vec<PolicyCheck>,
+}
+
+impl ManylinuxReport {
+    pub fn from(elf: &Elf) -> Self {
+        let init_functions = elf
+            .sections
+            .dynsym()
+            .unwrap_or(&[])
+            .iter()
+            .filter(|symbol| {
+                !symbol.is_undefined() && symbol.name_or_empty().starts_with("PyInit_")
+            })
+            .map(|symbol| symbol.name_or_empty().to_owned())
+            .collect();
+        let checks = MANYLINUX_POLICIES
+            .iter()
+            .map(|policy| PolicyCheck::from(elf, policy))
+            .collect();
+        Self {
+            init_functions,
+            checks,
+        }
+    }
+
+    /// The oldest policy the file complies with, which gives the most widely installable wheel tag
+    pub fn best_policy(&self) -> Option<&'static ManylinuxPolicy> {
+        self.checks
+            .iter()
+            .find(|check| check.is_compliant())
+            .map(|check| check.policy)
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Check", "Subject", "Result"]);
+        match self.init_functions.is_empty() {
+            true => table.push(vec![
+                String::from("Module"),
+                String::new(),
+                String::from("No PyInit_* function, this is not a Python extension module"),
+            ]),
+            false => {
+                for function in &self.init_functions {
+                    table.push(vec![
+                        String::from("Module"),
+                        function.to_owned(),
+                        format!(
+                            "Initializes module {}",
+                            function.trim_start_matches("PyInit_")
+                        ),
+                    ]);
+                }
+            }
+        }
+        table.push(vec![
+            String::from("Best policy"),
+            String::new(),
+            match self.best_policy() {
+                Some(policy) => policy.display_name(),
+                None => String::from("None"),
+            },
+        ]);
+        for check in &self.checks {
+            let name = check.policy.display_name();
+            if !check.is_applicable {
+                table.push(vec![
+                    name,
+                    String::new(),
+                    String::from("Not defined for this machine"),
+                ]);
+                continue;
+            }
+            match check.violations.is_empty() {
+                true => table.push(vec![name, String::new(), String::from("Compliant")]),
+                false => {
+                    for violation in &check.violations {
+                        table.push(vec![
+                            name.clone(),
+                            violation.subject.clone(),
+                            violation.detail.clone(),
+                        ]);
+                    }
+                }
+            }
+        }
+        table
+    }
+}
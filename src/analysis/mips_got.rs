@@ -0,0 +1,130 @@
+use crate::elf::sections::{SectionType, Symbol};
+use crate::elf::Elf;
+use crate::utils::Table;
+
+// dynamic tags describing the layout of the MIPS GOT
+const DT_PLTGOT: u64 = 3;
+const DT_MIPS_LOCAL_GOTNO: u64 = 0x7000000a;
+const DT_MIPS_SYMTABNO: u64 = 0x70000011;
+const DT_MIPS_GOTSYM: u64 = 0x70000013;
+
+pub enum GotEntryKind {
+    /// Entry 0, filled by the dynamic linker with the address of the lazy resolver
+    LazyResolver,
+    /// Entry 1 when its most significant bit is set (GNU extension), holds the link map of the object
+    ModulePointer,
+    Local,
+    Global,
+    /// Entries beyond the primary GOT, created by the linker when a single GOT can't be addressed from `$gp`
+    Secondary,
+}
+
+pub struct GotEntry {
+    pub index: usize,
+    pub address: u64,
+    pub value: u64,
+    pub kind: GotEntryKind,
+    pub symbol: Option<String>,
+}
+
+/// The GOT of a dynamically linked MIPS object, which is split into a local part
+/// (relocated by the load offset) and a global part (one entry per dynamic symbol starting at `DT_MIPS_GOTSYM`)
+pub struct MipsGot {
+    pub address: u64,
+    pub local_gotno: u64,
+    pub gotsym: u64,
+    pub symtabno: u64,
+    pub entries: Vec<GotEntry>,
+}
+
+impl MipsGot {
+    pub fn from(elf: &Elf) -> Option<Self> {
+        // EM_MIPS
+        if elf.hdr.e_machine.0 != 8 {
+            return None;
+        }
+        let dynamic = elf.sections.dynamic()?;
+        let address = dynamic.get(DT_PLTGOT)?;
+        let local_gotno = dynamic.get(DT_MIPS_LOCAL_GOTNO)?;
+        let gotsym = dynamic.get(DT_MIPS_GOTSYM)?;
+        let symtabno = dynamic.get(DT_MIPS_SYMTABNO)?;
+
+        let section = elf.sections.by_addr(address)?;
+        let data = section.raw()?;
+        let is_little_endian = elf.is_little_endian();
+        let entsize = match elf.is_64_bit() {
+            true => 8,
+            false => 4,
+        };
+
+        let dynsym: &[Symbol] = match elf.sections.0.iter().find(|s| s.sh_type() == 11) {
+            Some(section) => match &section.section_type {
+                SectionType::Symtab(symtab) => &symtab.symbols,
+                _ => &[],
+            },
+            None => &[],
+        };
+        let global_gotno = symtabno.saturating_sub(gotsym);
+
+        let mut entries = Vec::new();
+        let start = (address - section.addr) as usize;
+        let count = (data.get().len() - start) / entsize;
+        for index in 0..count {
+            let offset = start + index * entsize;
+            let value = match elf.is_64_bit() {
+                true => data.read_u64(offset, is_little_endian),
+                false => data.read_u32(offset, is_little_endian) as u64,
+            };
+            let msb = 1 << (entsize * 8 - 1);
+
+            let (kind, symbol) = match index as u64 {
+                0 => (GotEntryKind::LazyResolver, None),
+                1 if value & msb != 0 => (GotEntryKind::ModulePointer, None),
+                i if i < local_gotno => (GotEntryKind::Local, elf.symbolize(value)),
+                i if i < local_gotno + global_gotno => {
+                    let symbol = dynsym
+                        .get((gotsym + i - local_gotno) as usize)
+                        .map(|symbol| symbol.name_or_empty().to_owned());
+                    (GotEntryKind::Global, symbol)
+                }
+                _ => (GotEntryKind::Secondary, elf.symbolize(value)),
+            };
+            entries.push(GotEntry {
+                index,
+                address: address + (index * entsize) as u64,
+                value,
+                kind,
+                symbol,
+            });
+        }
+
+        Some(Self {
+            address,
+            local_gotno,
+            gotsym,
+            symtabno,
+            entries,
+        })
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Index", "Address", "Value", "Kind", "Symbol"]);
+        for entry in &self.entries {
+            let kind = match entry.kind {
+                GotEntryKind::LazyResolver => "Lazy resolver",
+                GotEntryKind::ModulePointer => "Module pointer",
+                GotEntryKind::Local => "Local",
+                GotEntryKind::Global => "Global",
+                GotEntryKind::Secondary => "Secondary GOT",
+            };
+            table.push(vec![
+                format!("{}", entry.index),
+                format!("0x{:x}", entry.address),
+                format!("0x{:x}", entry.value),
+                kind.to_owned(),
+                entry.symbol.clone().unwrap_or_default(),
+            ]);
+        }
+        table
+    }
+}
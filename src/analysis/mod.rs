@@ -0,0 +1,30 @@
+mod binding;
+pub use binding::*;
+mod checksec;
+pub use checksec::*;
+mod deps;
+pub use deps::*;
+mod entropy;
+pub use entropy::*;
+mod entry;
+pub use entry::*;
+mod imports;
+pub use imports::*;
+mod kmod;
+pub use kmod::*;
+mod layout;
+pub use layout::*;
+mod ldcache;
+pub use ldcache::*;
+mod manylinux;
+pub use manylinux::*;
+mod mips_got;
+pub use mips_got::*;
+mod modcheck;
+pub use modcheck::*;
+mod packer;
+pub use packer::*;
+mod pkcs7;
+pub use pkcs7::*;
+mod runtime;
+pub use runtime::*;
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use super::KernelModule;
+use crate::elf::sections::SectionType;
+use crate::elf::{Elf, ParsingError};
+use crate::utils::Table;
+
+/// Symbols exported by a kernel, loaded from a `Module.symvers` file or a vmlinux ELF
+pub struct KernelSymbols {
+    pub vermagic: Option<String>,
+    pub symbols: HashMap<String, KernelSymbol>,
+}
+
+pub struct KernelSymbol {
+    pub crc: Option<u64>,
+    pub module: String,
+    pub export_type: String,
+}
+
+impl KernelSymbols {
+    pub fn from(raw: Vec<u8>) -> Result<Self, ParsingError> {
+        match raw.starts_with(b"\x7fELF") {
+            true => Ok(Self::from_vmlinux(&Elf::from(raw)?)),
+            false => Ok(Self::from_symvers(&String::from_utf8_lossy(&raw))),
+        }
+    }
+
+    /// Each line of Module.symvers is `<crc>\t<symbol>\t<module>\t<export type>[\t<namespace>]`.
+    /// Older kernels put the namespace before the module instead of after the export type.
+    pub fn from_symvers(text: &str) -> Self {
+        let mut symbols = HashMap::new();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 3 {
+                continue;
+            }
+            let crc = u64::from_str_radix(fields[0].trim_start_matches("0x"), 16).ok();
+            let (module, export_type) = match fields.len() {
+                3 => (fields[2], ""),
+                4 => (fields[2], fields[3]),
+                _ => match fields[3].starts_with("EXPORT_") {
+                    true => (fields[2], fields[3]),
+                    false => (fields[3], fields[4]),
+                },
+            };
+            symbols.insert(
+                fields[1].to_owned(),
+                KernelSymbol {
+                    crc,
+                    module: module.to_owned(),
+                    export_type: export_type.to_owned(),
+                },
+            );
+        }
+        Self {
+            vermagic: None,
+            symbols,
+        }
+    }
+
+    /// Exported symbols have a `__ksymtab_<name>` symbol and, with CONFIG_MODVERSIONS,
+    /// a `__crc_<name>` symbol which is either absolute (value is the CRC) or points to the CRC.
+    pub fn from_vmlinux(elf: &Elf) -> Self {
+        let is_little_endian = elf.is_little_endian();
+        let mut symbols = HashMap::new();
+        let mut crcs = HashMap::new();
+
+        for symbol in elf.sections.symbols() {
+            let name = match &symbol.name {
+                None => continue,
+                Some(name) => name,
+            };
+            if let Some(name) = name.strip_prefix("__ksymtab_") {
+                let export_type = match elf.sections.0.get(symbol.st_shndx.0 as usize) {
+                    Some(section) if section.name.as_deref().unwrap_or("").contains("gpl") => {
+                        "EXPORT_SYMBOL_GPL"
+                    }
+                    _ => "EXPORT_SYMBOL",
+                };
+                symbols.insert(
+                    name.to_owned(),
+                    KernelSymbol {
+                        crc: None,
+                        module: "vmlinux".to_owned(),
+                        export_type: export_type.to_owned(),
+                    },
+                );
+            } else if let Some(name) = name.strip_prefix("__crc_") {
+                let crc = match symbol.st_shndx.0 {
+                    // ABS
+                    0xfff1 => Some(symbol.st_value),
+                    shndx => elf.sections.0.get(shndx as usize).and_then(|section| {
+                        let data = section.raw()?;
+                        let offset = symbol.st_value.checked_sub(section.addr)? as usize;
+                        match offset + 4 <= data.get().len() {
+                            true => Some(data.read_u32(offset, is_little_endian) as u64),
+                            false => None,
+                        }
+                    }),
+                };
+                crcs.insert(name.to_owned(), crc);
+            }
+        }
+        for (name, crc) in crcs {
+            if let Some(symbol) = symbols.get_mut(&name) {
+                symbol.crc = crc;
+            }
+        }
+
+        Self {
+            vermagic: Self::find_vermagic(elf),
+            symbols,
+        }
+    }
+
+    // the vermagic string starts with the kernel release, which can be found in the "Linux version" banner
+    fn find_vermagic(elf: &Elf) -> Option<String> {
+        let data = elf.sections.by_name(".rodata")?.data()?;
+        let banner = b"Linux version ";
+        let start = data.windows(banner.len()).position(|w| w == banner)? + banner.len();
+        let release_len = data[start..].iter().position(|&c| c == b' ')?;
+        let mut prefix = data[start..start + release_len].to_owned();
+        prefix.push(b' ');
+
+        data.split(|&c| c == b'\0')
+            .filter(|string| string.starts_with(&prefix))
+            .map(|string| String::from_utf8_lossy(string).into_owned())
+            .find(|string| string.contains("mod_unload"))
+    }
+}
+
+pub enum VermagicCheck {
+    Match,
+    /// Only the kernel release differs, which the kernel accepts if the module has symbol versions
+    ReleaseMismatch,
+    Mismatch,
+    Unknown,
+}
+
+#[derive(Clone)]
+pub enum SymbolStatus {
+    Ok,
+    CrcMismatch {
+        module_crc: u64,
+        kernel_crc: u64,
+    },
+    /// Exported without a CRC, which the kernel accepts with a warning
+    NoKernelCrc,
+    Missing,
+    /// Not exported by the kernel, but the module depends on other modules which may export it
+    MissingDependency,
+    /// Undefined weak symbols don't prevent loading
+    MissingWeak,
+}
+
+pub struct SymbolCheck {
+    pub name: String,
+    pub status: SymbolStatus,
+}
+
+pub struct CompatibilityReport {
+    pub module_vermagic: Option<String>,
+    pub kernel_vermagic: Option<String>,
+    pub vermagic: VermagicCheck,
+    pub versions: Vec<SymbolCheck>,
+    pub unresolved: Vec<SymbolCheck>,
+    pub kernel_symbols: usize,
+    /// Modules the module depends on, which aren't part of the kernel symbols
+    pub depends: Vec<String>,
+}
+
+impl CompatibilityReport {
+    /// `vermagic` overrides the vermagic of the kernel, which isn't available from Module.symvers.
+    /// It may also be just the kernel release, in which case only the release is compared
+    pub fn from(
+        elf: &Elf,
+        kmod: &KernelModule,
+        kernel: &KernelSymbols,
+        vermagic: Option<&str>,
+    ) -> Self {
+        let module_vermagic = kmod.vermagic().map(|v| v.to_owned());
+        let kernel_vermagic = vermagic
+            .map(|v| v.to_owned())
+            .or_else(|| kernel.vermagic.clone());
+        let vermagic = match (&module_vermagic, &kernel_vermagic) {
+            (Some(module), Some(kernel)) => {
+                let skip_release = |v: &str| {
+                    v.split_once(' ')
+                        .map(|(_, rest)| rest.trim_end().to_owned())
+                };
+                // only the release was given, so the rest of the vermagic can't be compared
+                let only_release = !kernel.trim().contains(' ');
+                let release_matches = module.split(' ').next() == Some(kernel.trim());
+                if module.trim_end() == kernel.trim_end() || (only_release && release_matches) {
+                    VermagicCheck::Match
+                } else if !kmod.versions.is_empty()
+                    && (only_release || skip_release(module) == skip_release(kernel))
+                {
+                    VermagicCheck::ReleaseMismatch
+                } else {
+                    VermagicCheck::Mismatch
+                }
+            }
+            _ => VermagicCheck::Unknown,
+        };
+
+        let depends: Vec<String> = kmod.depends().iter().map(|d| d.to_string()).collect();
+        let missing = match depends.is_empty() {
+            true => SymbolStatus::Missing,
+            false => SymbolStatus::MissingDependency,
+        };
+
+        let mut versions = Vec::new();
+        for version in &kmod.versions {
+            let status = match kernel.symbols.get(&version.name) {
+                None => missing.clone(),
+                Some(KernelSymbol { crc: None, .. }) => SymbolStatus::NoKernelCrc,
+                Some(KernelSymbol { crc: Some(crc), .. }) => {
+                    // CRCs are 32 bit, but are stored in an unsigned long
+                    match *crc as u32 == version.crc as u32 {
+                        true => SymbolStatus::Ok,
+                        false => SymbolStatus::CrcMismatch {
+                            module_crc: version.crc,
+                            kernel_crc: *crc,
+                        },
+                    }
+                }
+            };
+            versions.push(SymbolCheck {
+                name: version.name.to_owned(),
+                status,
+            });
+        }
+
+        let mut unresolved = Vec::new();
+        if let Some(SectionType::Symtab(symtab)) =
+            elf.sections.by_name(".symtab").map(|s| &s.section_type)
+        {
+            for symbol in symtab.symbols.iter().skip(1) {
+                let name = symbol.name_or_empty();
+                if !symbol.is_undefined() || name.is_empty() || symbol.bind().0 == 0 {
+                    continue;
+                }
+                // symbols with a version are already covered by the CRC checks
+                if kernel.symbols.contains_key(name) || kmod.versions.iter().any(|v| v.name == name)
+                {
+                    continue;
+                }
+                unresolved.push(SymbolCheck {
+                    name: name.to_owned(),
+                    status: match symbol.bind().0 {
+                        2 => SymbolStatus::MissingWeak,
+                        _ => missing.clone(),
+                    },
+                });
+            }
+        }
+
+        Self {
+            module_vermagic,
+            kernel_vermagic,
+            vermagic,
+            versions,
+            unresolved,
+            kernel_symbols: kernel.symbols.len(),
+            depends,
+        }
+    }
+
+    pub fn crc_mismatches(&self) -> impl Iterator<Item = &SymbolCheck> {
+        self.versions
+            .iter()
+            .filter(|check| matches!(check.status, SymbolStatus::CrcMismatch { .. }))
+    }
+
+    pub fn would_load(&self) -> bool {
+        !matches!(self.vermagic, VermagicCheck::Mismatch)
+            && self.versions.iter().all(|check| {
+                matches!(
+                    check.status,
+                    SymbolStatus::Ok | SymbolStatus::NoKernelCrc | SymbolStatus::MissingDependency
+                )
+            })
+            && self.unresolved.iter().all(|check| {
+                matches!(
+                    check.status,
+                    SymbolStatus::MissingWeak | SymbolStatus::MissingDependency
+                )
+            })
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Status", "Item", "Details"]);
+        let na = || "N/A".to_owned();
+
+        let from_depends = self
+            .versions
+            .iter()
+            .chain(self.unresolved.iter())
+            .any(|check| matches!(check.status, SymbolStatus::MissingDependency));
+        table.push(vec![
+            match self.would_load() {
+                true => "OK".to_owned(),
+                false => "FAIL".to_owned(),
+            },
+            "Verdict".to_owned(),
+            match (self.would_load(), from_depends) {
+                (true, false) => "The module should load on this kernel".to_owned(),
+                (true, true) => {
+                    "The module should load on this kernel if its dependencies export the missing symbols"
+                        .to_owned()
+                }
+                (false, _) => "The module would be rejected by this kernel".to_owned(),
+            },
+        ]);
+
+        let (status, details) = match self.vermagic {
+            VermagicCheck::Match => ("OK", "vermagic matches"),
+            VermagicCheck::ReleaseMismatch => (
+                "WARN",
+                "Kernel release differs, accepted because the module has symbol versions",
+            ),
+            VermagicCheck::Mismatch => ("FAIL", "vermagic doesn't match"),
+            VermagicCheck::Unknown => ("?", "vermagic of the kernel is unknown"),
+        };
+        table.push(vec![
+            status.to_owned(),
+            "vermagic".to_owned(),
+            format!(
+                "{} (module: {}, kernel: {})",
+                details,
+                self.module_vermagic.clone().unwrap_or_else(na),
+                self.kernel_vermagic.clone().unwrap_or_else(na)
+            ),
+        ]);
+
+        for check in self.versions.iter().chain(self.unresolved.iter()) {
+            let (status, details) = match &check.status {
+                SymbolStatus::Ok => continue,
+                SymbolStatus::CrcMismatch {
+                    module_crc,
+                    kernel_crc,
+                } => (
+                    "FAIL",
+                    format!(
+                        "Disagrees about version of symbol (module: 0x{:08x}, kernel: 0x{:08x})",
+                        module_crc, kernel_crc
+                    ),
+                ),
+                SymbolStatus::NoKernelCrc => (
+                    "WARN",
+                    "The kernel exports this symbol without a CRC".to_owned(),
+                ),
+                SymbolStatus::Missing => (
+                    "FAIL",
+                    "Unknown symbol (not exported by the kernel)".to_owned(),
+                ),
+                SymbolStatus::MissingDependency => (
+                    "WARN",
+                    format!(
+                        "Not exported by the kernel, expected from a dependency (depends: {})",
+                        self.depends.join(", ")
+                    ),
+                ),
+                SymbolStatus::MissingWeak => {
+                    ("WARN", "Weak symbol not exported by the kernel".to_owned())
+                }
+            };
+            table.push(vec![status.to_owned(), check.name.to_owned(), details]);
+        }
+
+        let ok = self
+            .versions
+            .iter()
+            .filter(|check| matches!(check.status, SymbolStatus::Ok))
+            .count();
+        table.push(vec![
+            "INFO".to_owned(),
+            "Symbol versions".to_owned(),
+            format!("{} of {} CRCs match", ok, self.versions.len()),
+        ]);
+        table.push(vec![
+            "INFO".to_owned(),
+            "Kernel symbols".to_owned(),
+            format!("{} exported symbols loaded", self.kernel_symbols),
+        ]);
+        table
+    }
+}
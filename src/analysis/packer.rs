@@ -0,0 +1,471 @@
+use super::EntropyReport;
+use crate::elf::{Description, Elf, Segment};
+use crate::utils::{RcSlice, Table};
+
+const UPX_MAGIC: &[u8] = b"UPX!";
+
+/// Version string UPX embeds in its decompression stub
+const UPX_ID: &[u8] = b"$Id: UPX ";
+
+// structure sizes
+const L_INFO_SIZE: usize = 12;
+const P_INFO_SIZE: usize = 12;
+const PACK_HEADER_SIZE: usize = 32;
+
+/// Segments which occupy this many times more memory than file space are suspicious
+const MEMSZ_RATIO: u64 = 4;
+
+/// Extra memory below this is ordinary `.bss`, whatever the ratio
+const MEMSZ_MIN_EXTRA: u64 = 0x10000;
+
+// segment flags
+const PF_RWX: u32 = 0x7;
+
+// segment types
+const PT_LOAD: u32 = 1;
+
+/// `l_info`, the loader header UPX puts after the program headers of a packed ELF file
+pub struct UpxLInfo {
+    pub offset: usize,
+    pub l_checksum: u32,
+    /// Size of the decompression stub
+    pub l_lsize: u16,
+    pub l_version: u8,
+    pub l_format: UpxFormat,
+}
+
+/// `p_info`, the program header which follows `l_info`
+pub struct UpxPInfo {
+    pub offset: usize,
+    pub p_progid: u32,
+    /// Size of the original file
+    pub p_filesize: u32,
+    /// Size of the blocks the original file was compressed in
+    pub p_blocksize: u32,
+}
+
+/// `PackHeader`, the header UPX writes after the compressed data
+pub struct UpxPackHeader {
+    pub offset: usize,
+    pub version: u8,
+    pub format: UpxFormat,
+    pub method: UpxMethod,
+    pub level: u8,
+    pub u_adler: u32,
+    pub c_adler: u32,
+    /// Size of the data before compression
+    pub u_len: u32,
+    /// Size of the data after compression
+    pub c_len: u32,
+    pub u_file_size: u32,
+    pub filter: u8,
+    pub filter_cto: u8,
+}
+
+/// UPX structures found in the file
+pub struct UpxInfo {
+    /// Version from the `$Id: UPX ...` string of the stub, e.g. `3.96`
+    pub version: Option<String>,
+    pub l_info: Option<UpxLInfo>,
+    pub p_info: Option<UpxPInfo>,
+    pub pack_header: Option<UpxPackHeader>,
+}
+
+impl UpxInfo {
+    /// Look for the UPX structures. Returns None unless the file has a valid `l_info` or a plausible `PackHeader`
+    pub fn from(elf: &Elf) -> Option<Self> {
+        let data = elf.raw.get();
+
+        // l_info directly follows the program header table, and p_info directly follows l_info
+        let is_little_endian = elf.is_little_endian();
+        let hdr = &elf.hdr;
+        let l_info_offset =
+            hdr.e_phoff.to_usize() + hdr.e_phnum as usize * hdr.e_phentsize as usize;
+        let l_info = data
+            .get(l_info_offset..l_info_offset + L_INFO_SIZE + P_INFO_SIZE)
+            .filter(|raw| &raw[4..8] == UPX_MAGIC && UpxFormat(raw[11]).name().is_some())
+            .map(|_| {
+                let raw = RcSlice::from(&elf.raw, l_info_offset, l_info_offset + L_INFO_SIZE);
+                UpxLInfo {
+                    offset: l_info_offset,
+                    l_checksum: raw.read_u32(0, is_little_endian),
+                    l_lsize: raw.read_u16(8, is_little_endian),
+                    l_version: raw.get()[10],
+                    l_format: UpxFormat(raw.get()[11]),
+                }
+            });
+        let p_info = l_info.as_ref().map(|l_info| {
+            let offset = l_info.offset + L_INFO_SIZE;
+            let raw = RcSlice::from(&elf.raw, offset, offset + P_INFO_SIZE);
+            UpxPInfo {
+                offset,
+                p_progid: raw.read_u32(0, is_little_endian),
+                p_filesize: raw.read_u32(4, is_little_endian),
+                p_blocksize: raw.read_u32(8, is_little_endian),
+            }
+        });
+
+        // the last signature is the PackHeader at the end of the compressed data
+        let pack_header = find_all(data, UPX_MAGIC)
+            .iter()
+            .rev()
+            .find_map(|offset| Self::pack_header(&elf.raw, *offset));
+
+        if l_info.is_none() && pack_header.is_none() {
+            return None;
+        }
+        let version = find_all(data, UPX_ID)
+            .first()
+            .map(|offset| {
+                data[offset + UPX_ID.len()..]
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_graphic())
+                    .map(|byte| *byte as char)
+                    .collect::<String>()
+            })
+            .filter(|version| !version.is_empty());
+
+        Some(Self {
+            version,
+            l_info,
+            p_info,
+            pack_header,
+        })
+    }
+
+    fn pack_header(raw: &RcSlice<u8>, offset: usize) -> Option<UpxPackHeader> {
+        if offset + PACK_HEADER_SIZE > raw.get().len() {
+            return None;
+        }
+        let raw = RcSlice::from(raw, offset, offset + PACK_HEADER_SIZE);
+        let data = raw.get();
+        let (version, format, method) = (data[4], data[5], data[6]);
+        // skip signatures which are not followed by a plausible header
+        if version == 0
+            || version > 0x20
+            || UpxFormat(format).name().is_none()
+            || UpxMethod(method).name().is_none()
+        {
+            return None;
+        }
+        // unlike l_info and p_info, the PackHeader is little endian whatever the target
+        let is_little_endian = true;
+        let u_len = raw.read_u32(16, is_little_endian);
+        let c_len = raw.read_u32(20, is_little_endian);
+        // UPX refuses to pack data which doesn't compress
+        if c_len == 0 || c_len >= u_len {
+            return None;
+        }
+        Some(UpxPackHeader {
+            offset,
+            version,
+            format: UpxFormat(format),
+            method: UpxMethod(method),
+            level: data[7],
+            u_adler: raw.read_u32(8, is_little_endian),
+            c_adler: raw.read_u32(12, is_little_endian),
+            u_len,
+            c_len,
+            u_file_size: raw.read_u32(24, is_little_endian),
+            filter: data[28],
+            filter_cto: data[29],
+        })
+    }
+}
+
+/// Signature and heuristic detection of packers and protectors
+pub struct PackerReport {
+    pub upx: Option<UpxInfo>,
+    /// There is no section header table
+    pub is_missing_sections: bool,
+    /// Index of the only PT_LOAD segment, when it is readable, writable and executable
+    pub single_rwx_load: Option<usize>,
+    /// PT_LOAD segments whose file and memory sizes are far apart
+    pub size_mismatches: Vec<Segment>,
+    /// Executable code with the entropy of compressed or encrypted data
+    pub is_high_entropy: bool,
+}
+
+impl PackerReport {
+    pub fn from(elf: &Elf) -> Self {
+        let loads: Vec<Segment> = elf
+            .phdr_table
+            .segments()
+            .into_iter()
+            .filter(|segment| segment.p_type == PT_LOAD)
+            .collect();
+        let single_rwx_load = match loads.as_slice() {
+            [load] if load.p_flags & PF_RWX == PF_RWX => Some(load.index),
+            _ => None,
+        };
+        let size_mismatches = loads
+            .into_iter()
+            .filter(|segment| {
+                // more file contents than memory is invalid, and executable segments with no file contents are
+                // filled at run time
+                segment.filesz > segment.memsz
+                    || (segment.filesz == 0 && segment.memsz != 0 && segment.is_executable())
+                    || (segment.memsz - segment.filesz >= MEMSZ_MIN_EXTRA
+                        && segment.memsz / segment.filesz.max(1) >= MEMSZ_RATIO)
+            })
+            .collect();
+        Self {
+            upx: UpxInfo::from(elf),
+            is_missing_sections: elf.hdr.e_shoff.to_u64() == 0 || elf.hdr.e_shnum == 0,
+            single_rwx_load,
+            size_mismatches,
+            is_high_entropy: EntropyReport::from(elf).is_likely_packed(),
+        }
+    }
+
+    /// Conclusion drawn from the signatures and heuristics
+    pub fn verdict(&self) -> String {
+        if let Some(upx) = &self.upx {
+            return match &upx.version {
+                Some(version) => format!("Packed with UPX {}", version),
+                None => String::from("Packed with UPX"),
+            };
+        }
+        let heuristics = [
+            self.is_missing_sections && self.single_rwx_load.is_some(),
+            !self.size_mismatches.is_empty(),
+            self.is_high_entropy,
+        ];
+        match heuristics.iter().filter(|hit| **hit).count() {
+            0 => String::from("No packer detected"),
+            1 => String::from("Possibly packed or protected"),
+            _ => String::from("Likely packed or protected"),
+        }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Check", "Result", "Details"]);
+        let mut push = |check: &str, result: String, details: String| {
+            table.push(vec![check.to_owned(), result, details]);
+        };
+        push("Verdict", self.verdict(), String::new());
+
+        match &self.upx {
+            None => push("UPX signature", String::from("Not found"), String::new()),
+            Some(upx) => {
+                push(
+                    "UPX signature",
+                    String::from("Detected"),
+                    match &upx.version {
+                        Some(version) => format!("$Id: UPX {}", version),
+                        None => String::new(),
+                    },
+                );
+                if let Some(l_info) = &upx.l_info {
+                    push(
+                        "UPX l_info",
+                        format!("0x{:x}", l_info.offset),
+                        format!(
+                            "l_checksum 0x{:08x}, l_lsize 0x{:x}, l_version {}, l_format {}",
+                            l_info.l_checksum,
+                            l_info.l_lsize,
+                            l_info.l_version,
+                            l_info.l_format.to_str()
+                        ),
+                    );
+                }
+                if let Some(p_info) = &upx.p_info {
+                    push(
+                        "UPX p_info",
+                        format!("0x{:x}", p_info.offset),
+                        format!(
+                            "p_progid 0x{:x}, p_filesize 0x{:x}, p_blocksize 0x{:x}",
+                            p_info.p_progid, p_info.p_filesize, p_info.p_blocksize
+                        ),
+                    );
+                }
+                if let Some(header) = &upx.pack_header {
+                    push(
+                        "UPX PackHeader",
+                        format!("0x{:x}", header.offset),
+                        format!(
+                            "version {}, format {}, u_adler 0x{:08x}, c_adler 0x{:08x}",
+                            header.version,
+                            header.format.to_str(),
+                            header.u_adler,
+                            header.c_adler
+                        ),
+                    );
+                    push(
+                        "UPX method",
+                        header.method.to_str(),
+                        format!(
+                            "level {}, filter 0x{:x}, filter_cto 0x{:x}",
+                            header.level, header.filter, header.filter_cto
+                        ),
+                    );
+                    push(
+                        "UPX packed size",
+                        format!("0x{:x}", header.c_len),
+                        match header.u_len {
+                            0 => String::new(),
+                            u_len => format!(
+                                "{:.1}% of the unpacked size",
+                                header.c_len as f64 * 100.0 / u_len as f64
+                            ),
+                        },
+                    );
+                    push(
+                        "UPX unpacked size",
+                        format!("0x{:x}", header.u_len),
+                        format!("Original file size 0x{:x}", header.u_file_size),
+                    );
+                }
+            }
+        }
+
+        push(
+            "Section header table",
+            match self.is_missing_sections {
+                true => String::from("Missing"),
+                false => String::from("Present"),
+            },
+            String::new(),
+        );
+        push(
+            "Single RWX PT_LOAD",
+            match (self.is_missing_sections, self.single_rwx_load) {
+                (true, Some(_)) => String::from("Detected"),
+                (false, Some(_)) => String::from("Present (with section headers)"),
+                (_, None) => String::from("Not found"),
+            },
+            match self.single_rwx_load {
+                Some(index) => format!("Segment {:02} is the only PT_LOAD and is RWE", index),
+                None => String::new(),
+            },
+        );
+        match self.size_mismatches.is_empty() {
+            true => push(
+                "p_filesz / p_memsz",
+                String::from("Consistent"),
+                String::new(),
+            ),
+            false => {
+                for segment in &self.size_mismatches {
+                    push(
+                        "p_filesz / p_memsz",
+                        format!("Segment {:02}", segment.index),
+                        format!(
+                            "p_filesz 0x{:x}, p_memsz 0x{:x} ({})",
+                            segment.filesz,
+                            segment.memsz,
+                            match segment.filesz > segment.memsz {
+                                true => String::from("more file contents than memory"),
+                                false => format!(
+                                    "memory is {}x the file contents",
+                                    segment.memsz / segment.filesz.max(1)
+                                ),
+                            }
+                        ),
+                    );
+                }
+            }
+        }
+        push(
+            "High entropy code",
+            match self.is_high_entropy {
+                true => String::from("Detected"),
+                false => String::from("Not found"),
+            },
+            String::new(),
+        );
+        table
+    }
+}
+
+/// UPX compression method (`M_*`)
+pub struct UpxMethod(pub u8);
+
+impl UpxMethod {
+    fn name(&self) -> Option<&'static str> {
+        Some(match self.0 {
+            2 => "NRV2B_LE32",
+            3 => "NRV2B_8",
+            4 => "NRV2B_LE16",
+            5 => "NRV2D_LE32",
+            6 => "NRV2D_8",
+            7 => "NRV2D_LE16",
+            8 => "NRV2E_LE32",
+            9 => "NRV2E_8",
+            10 => "NRV2E_LE16",
+            11 => "CL1B_LE32",
+            12 => "CL1B_8",
+            13 => "CL1B_LE16",
+            14 => "LZMA",
+            15 => "DEFLATE",
+            16 => "ZSTD",
+            _ => return None,
+        })
+    }
+}
+
+impl Description for UpxMethod {
+    fn to_str(&self) -> String {
+        match self.name() {
+            Some(name) => name.to_owned(),
+            None => format!("<unknown: 0x{:x}>", self.0),
+        }
+    }
+}
+
+/// UPX executable format (`UPX_F_*`)
+pub struct UpxFormat(pub u8);
+
+impl UpxFormat {
+    fn name(&self) -> Option<&'static str> {
+        Some(match self.0 {
+            10 => "LINUX_i386",
+            12 => "LINUX_ELF_i386",
+            13 => "LINUX_SEP_i386",
+            14 => "LINUX_SH_i386",
+            15 => "VMLINUZ_i386",
+            16 => "BVMLINUZ_i386",
+            18 => "PS1_EXE",
+            19 => "VMLINUX_i386",
+            20 => "LINUX_ELFI_i386",
+            22 => "LINUX_ELF64_AMD",
+            23 => "LINUX_ELF32_ARMEL",
+            24 => "BSD_i386",
+            25 => "BSD_ELF_i386",
+            26 => "BSD_SH_i386",
+            27 => "VMLINUX_AMD64",
+            28 => "VMLINUX_ARMEL",
+            30 => "LINUX_ELF32_MIPSEL",
+            31 => "VMLINUZ_ARMEL",
+            39 => "LINUX_ELFPPC64LE",
+            40 => "VMLINUX_PPC64LE",
+            42 => "LINUX_ELF64_ARM",
+            132 => "LINUX_ELFPPC32",
+            133 => "LINUX_ELF32_ARMEB",
+            135 => "VMLINUX_ARMEB",
+            136 => "VMLINUX_PPC32",
+            137 => "LINUX_ELF32_MIPSEB",
+            140 => "LINUX_ELFPPC64",
+            141 => "VMLINUX_PPC64",
+            _ => return None,
+        })
+    }
+}
+
+impl Description for UpxFormat {
+    fn to_str(&self) -> String {
+        match self.name() {
+            Some(name) => name.to_owned(),
+            None => format!("<unknown: 0x{:x}>", self.0),
+        }
+    }
+}
+
+/// Offsets of every occurrence of a byte string
+fn find_all(data: &[u8], needle: &[u8]) -> Vec<usize> {
+    data.windows(needle.len())
+        .enumerate()
+        .filter(|(_, window)| *window == needle)
+        .map(|(offset, _)| offset)
+        .collect()
+}
@@ -0,0 +1,211 @@
+// Minimal DER walker used to extract signer metadata from PKCS#7 signatures.
+// This is not a general purpose ASN.1 parser: it only collects the object identifiers,
+// strings and integers of the structure (in order) and doesn't verify anything.
+
+enum DerItem {
+    Oid(String),
+    Str(String),
+    Int(Vec<u8>),
+}
+
+pub struct Pkcs7Info {
+    pub content_type: Option<String>,
+    pub digest_algorithm: Option<String>,
+    pub signature_algorithm: Option<String>,
+    pub issuer: Vec<(String, String)>,
+    pub serial: Option<Vec<u8>>,
+}
+
+impl Pkcs7Info {
+    pub fn from(data: &[u8]) -> Option<Self> {
+        let mut items = Vec::new();
+        if !der_walk(data, &mut items, 0) || items.is_empty() {
+            return None;
+        }
+
+        let mut content_type = None;
+        let mut digest_algorithm = None;
+        let mut signature_algorithm = None;
+        let mut issuer = Vec::new();
+        let mut last_attribute = None;
+
+        for (i, item) in items.iter().enumerate() {
+            let oid = match item {
+                DerItem::Oid(oid) => oid,
+                _ => continue,
+            };
+            if content_type.is_none() {
+                content_type = Some(oid_name(oid));
+            }
+            if digest_algorithm.is_none() && is_digest_oid(oid) {
+                digest_algorithm = Some(oid_name(oid));
+            }
+            if signature_algorithm.is_none() && is_signature_oid(oid) {
+                signature_algorithm = Some(oid_name(oid));
+            }
+            if let Some(attribute) = attribute_name(oid) {
+                if let Some(DerItem::Str(value)) = items.get(i + 1) {
+                    issuer.push((attribute.to_owned(), value.to_owned()));
+                    last_attribute = Some(i + 1);
+                }
+            }
+        }
+
+        // the serial number directly follows the issuer name in the signer info
+        let serial = match last_attribute {
+            None => None,
+            Some(index) => items[index..].iter().find_map(|item| match item {
+                DerItem::Int(value) => Some(value.to_owned()),
+                _ => None,
+            }),
+        };
+
+        Some(Self {
+            content_type,
+            digest_algorithm,
+            signature_algorithm,
+            issuer,
+            serial,
+        })
+    }
+}
+
+fn der_walk(data: &[u8], items: &mut Vec<DerItem>, depth: usize) -> bool {
+    // protect against maliciously nested structures
+    if depth > 32 {
+        return false;
+    }
+
+    let mut offset = 0;
+    while offset < data.len() {
+        let tag = data[offset];
+        // multi-byte tags are not used by PKCS#7
+        if tag & 0x1f == 0x1f {
+            return false;
+        }
+        let (len, len_size) = match der_length(&data[offset + 1..]) {
+            None => return false,
+            Some(len) => len,
+        };
+        let start = offset + 1 + len_size;
+        let end = match start.checked_add(len) {
+            Some(end) if end <= data.len() => end,
+            _ => return false,
+        };
+        let content = &data[start..end];
+
+        if tag & 0x20 != 0 {
+            // constructed
+            if !der_walk(content, items, depth + 1) {
+                return false;
+            }
+        } else {
+            match tag {
+                0x02 => items.push(DerItem::Int(content.to_owned())),
+                0x06 => items.push(DerItem::Oid(decode_oid(content))),
+                0x0c | 0x13 | 0x14 | 0x16 => {
+                    items.push(DerItem::Str(String::from_utf8_lossy(content).into_owned()))
+                }
+                _ => (),
+            }
+        }
+        offset = end;
+    }
+    true
+}
+
+fn der_length(data: &[u8]) -> Option<(usize, usize)> {
+    let first = *data.first()?;
+    if first & 0x80 == 0 {
+        return Some((first as usize, 1));
+    }
+    let count = (first & 0x7f) as usize;
+    // indefinite lengths are not allowed in DER
+    if count == 0 || count > 4 || data.len() < count + 1 {
+        return None;
+    }
+    let mut len = 0;
+    for byte in &data[1..count + 1] {
+        len = (len << 8) | *byte as usize;
+    }
+    Some((len, count + 1))
+}
+
+fn decode_oid(data: &[u8]) -> String {
+    let mut components = Vec::new();
+    let mut value: u64 = 0;
+    for byte in data {
+        value = (value << 7) | (byte & 0x7f) as u64;
+        if byte & 0x80 == 0 {
+            if components.is_empty() {
+                let first = std::cmp::min(value / 40, 2);
+                components.push(first);
+                components.push(value - first * 40);
+            } else {
+                components.push(value);
+            }
+            value = 0;
+        }
+    }
+    components
+        .iter()
+        .map(|component| component.to_string())
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+fn is_digest_oid(oid: &str) -> bool {
+    oid.starts_with("2.16.840.1.101.3.4.2.")
+        || oid == "1.3.14.3.2.26"
+        || oid == "1.2.156.10197.1.401"
+}
+
+fn is_signature_oid(oid: &str) -> bool {
+    oid.starts_with("1.2.840.113549.1.1.")
+        || oid.starts_with("1.2.840.10045.")
+        || oid == "1.3.101.112"
+        || oid == "1.2.156.10197.1.501"
+}
+
+fn attribute_name(oid: &str) -> Option<&'static str> {
+    match oid {
+        "2.5.4.3" => Some("CN"),
+        "2.5.4.6" => Some("C"),
+        "2.5.4.7" => Some("L"),
+        "2.5.4.8" => Some("ST"),
+        "2.5.4.10" => Some("O"),
+        "2.5.4.11" => Some("OU"),
+        "1.2.840.113549.1.9.1" => Some("emailAddress"),
+        _ => None,
+    }
+}
+
+fn oid_name(oid: &str) -> String {
+    let name = match oid {
+        "1.2.840.113549.1.7.1" => "data",
+        "1.2.840.113549.1.7.2" => "signedData",
+        "1.3.14.3.2.26" => "sha1",
+        "2.16.840.1.101.3.4.2.1" => "sha256",
+        "2.16.840.1.101.3.4.2.2" => "sha384",
+        "2.16.840.1.101.3.4.2.3" => "sha512",
+        "2.16.840.1.101.3.4.2.4" => "sha224",
+        "2.16.840.1.101.3.4.2.8" => "sha3-256",
+        "2.16.840.1.101.3.4.2.9" => "sha3-384",
+        "2.16.840.1.101.3.4.2.10" => "sha3-512",
+        "1.2.156.10197.1.401" => "sm3",
+        "1.2.840.113549.1.1.1" => "rsaEncryption",
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption",
+        "1.2.840.113549.1.1.10" => "RSASSA-PSS",
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption",
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption",
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption",
+        "1.2.840.10045.2.1" => "ecPublicKey",
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256",
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384",
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512",
+        "1.3.101.112" => "Ed25519",
+        "1.2.156.10197.1.501" => "SM2-with-SM3",
+        other => return other.to_owned(),
+    };
+    name.to_owned()
+}
@@ -0,0 +1,92 @@
+use crate::elf::Elf;
+use crate::utils::Table;
+
+/// Version families whose highest required version is the minimum runtime of the file
+const FAMILIES: [&str; 3] = ["GLIBC_", "GLIBCXX_", "CXXABI_"];
+
+/// The highest version of a family required by the file, and the symbols which require it
+pub struct VersionFloor {
+    /// Prefix of the version names, e.g. `GLIBC_`
+    pub family: String,
+    /// Full version name, e.g. `GLIBC_2.17`
+    pub version: String,
+    /// Library the version is required from
+    pub library: Option<String>,
+    pub symbols: Vec<String>,
+}
+
+/// Minimum versions of glibc and libstdc++ needed to run the file, from its version requirements (`.gnu.version_r`)
+pub struct RuntimeRequirements(pub Vec<VersionFloor>);
+
+impl RuntimeRequirements {
+    pub fn from(elf: &Elf) -> Self {
+        let verneed = match elf.sections.verneed() {
+            Some(verneed) => verneed,
+            None => return Self(Vec::new()),
+        };
+
+        let mut floors = Vec::new();
+        for family in FAMILIES {
+            // highest numeric version of the family (GLIBC_PRIVATE and the like are skipped)
+            let highest = verneed
+                .files
+                .iter()
+                .flat_map(|file| file.versions.iter().map(move |version| (file, version)))
+                .filter_map(|(file, version)| {
+                    let name = version.name.as_deref()?;
+                    let number = parse_version(name.strip_prefix(family)?)?;
+                    Some((number, name, file.file.clone()))
+                })
+                .max_by(|a, b| a.0.cmp(&b.0));
+            let (_, version, library) = match highest {
+                Some(highest) => highest,
+                None => continue,
+            };
+
+            let symbols = elf
+                .sections
+                .dynsym()
+                .unwrap_or(&[])
+                .iter()
+                .filter(|symbol| symbol.is_undefined())
+                .filter(|symbol| {
+                    elf.sections
+                        .symbol_version(symbol.index)
+                        .is_some_and(|symbol_version| symbol_version.name == version)
+                })
+                .map(|symbol| symbol.name_or_empty().to_owned())
+                .collect();
+            floors.push(VersionFloor {
+                family: family.to_owned(),
+                version: version.to_owned(),
+                library,
+                symbols,
+            });
+        }
+        Self(floors)
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Family", "Minimum Version", "Library", "Required By"]);
+        for floor in &self.0 {
+            table.push(vec![
+                floor.family.trim_end_matches('_').to_owned(),
+                floor.version.clone(),
+                floor.library.clone().unwrap_or_default(),
+                match floor.symbols.is_empty() {
+                    true => String::from("(no symbol, only the version requirement)"),
+                    false => floor.symbols.join(", "),
+                },
+            ]);
+        }
+        table
+    }
+}
+
+/// Split a version like `2.2.5` into its numbers, so that `2.17` sorts after `2.3.4`
+pub(super) fn parse_version(version: &str) -> Option<Vec<u32>> {
+    version
+        .split('.')
+        .map(|part| part.parse::<u32>().ok())
+        .collect()
+}
@@ -0,0 +1,131 @@
+use super::sections::Section;
+use super::{Elf, Segment};
+
+const ET_REL: u16 = 1;
+const PT_LOAD: u32 = 1;
+
+/// Where a virtual address or file offset lands in the file
+pub struct Location {
+    pub vaddr: Option<u64>,
+    /// None when the address has no bytes in the file
+    pub offset: Option<u64>,
+    /// Index of the PT_LOAD program header which contains the location
+    pub segment: Option<usize>,
+    /// Index of the section which contains the location
+    pub section: Option<usize>,
+    /// The address is in the zero filled part of a segment (`p_memsz > p_filesz`) or in a NOBITS section, like `.bss`
+    pub is_bss: bool,
+}
+
+impl Elf {
+    /// Loadable segments, which define the mapping between addresses and offsets
+    pub fn load_segments(&self) -> Vec<Segment> {
+        self.phdr_table
+            .segments()
+            .into_iter()
+            .filter(|segment| segment.p_type == PT_LOAD)
+            .collect()
+    }
+
+    /// Find where a virtual address is mapped from, using the PT_LOAD segments.
+    /// Files without loadable segments fall back to the addresses in the section headers.
+    /// Relocatable files give None, since all of their sections start at address 0
+    pub fn locate_vaddr(&self, vaddr: u64) -> Option<Location> {
+        if self.hdr.e_type.0 == ET_REL {
+            return None;
+        }
+        let section = self.section_by_vaddr(vaddr);
+        let segments = self.load_segments();
+
+        if segments.is_empty() {
+            let section = section?;
+            let delta = vaddr - section.addr;
+            return Some(Location {
+                vaddr: Some(vaddr),
+                offset: match section.is_nobits() {
+                    true => None,
+                    false => Some(section.file_offset as u64 + delta),
+                },
+                segment: None,
+                section: Some(section.index),
+                is_bss: section.is_nobits(),
+            });
+        }
+
+        let segment = segments
+            .iter()
+            .find(|segment| vaddr >= segment.vaddr && vaddr - segment.vaddr < segment.memsz)?;
+        let delta = vaddr - segment.vaddr;
+        let in_file = delta < segment.filesz;
+        let is_nobits = section.is_some_and(|section| section.is_nobits());
+        Some(Location {
+            vaddr: Some(vaddr),
+            offset: match in_file && !is_nobits {
+                true => Some(segment.offset + delta),
+                false => None,
+            },
+            segment: Some(segment.index),
+            section: section.map(|section| section.index),
+            is_bss: !in_file || is_nobits,
+        })
+    }
+
+    /// Find which segment and section a file offset belongs to, and the address it is loaded at (if any)
+    pub fn locate_offset(&self, offset: u64) -> Option<Location> {
+        if offset >= self.raw.get().len() as u64 {
+            return None;
+        }
+        let section = self.section_by_offset(offset);
+        let segment = self
+            .load_segments()
+            .into_iter()
+            .find(|segment| offset >= segment.offset && offset - segment.offset < segment.filesz);
+
+        let vaddr = match (&segment, section) {
+            (Some(segment), _) => Some(segment.vaddr + offset - segment.offset),
+            // without loadable segments, sections are placed at their own addresses
+            (None, Some(section)) if section.is_alloc() && self.load_segments().is_empty() => {
+                Some(section.addr + offset - section.file_offset as u64)
+            }
+            _ => None,
+        };
+        Some(Location {
+            vaddr,
+            offset: Some(offset),
+            segment: segment.map(|segment| segment.index),
+            section: section.map(|section| section.index),
+            is_bss: false,
+        })
+    }
+
+    pub fn vaddr_to_offset(&self, vaddr: u64) -> Option<u64> {
+        self.locate_vaddr(vaddr)?.offset
+    }
+
+    pub fn offset_to_vaddr(&self, offset: u64) -> Option<u64> {
+        self.locate_offset(offset)?.vaddr
+    }
+
+    /// Find the allocated section which contains a virtual address.
+    /// `.tbss` is skipped because it only describes the TLS template and occupies no addresses of its own
+    pub fn section_by_vaddr(&self, vaddr: u64) -> Option<&Section> {
+        self.sections.0.iter().find(|section| {
+            section.is_alloc()
+                && section.size != 0
+                && !(section.is_tls() && section.is_nobits())
+                && vaddr >= section.addr
+                && vaddr - section.addr < section.size as u64
+        })
+    }
+
+    /// Find the section whose contents in the file contain an offset
+    pub fn section_by_offset(&self, offset: u64) -> Option<&Section> {
+        self.sections.0.iter().find(|section| {
+            section.index != 0
+                && !section.is_nobits()
+                && section.size != 0
+                && offset >= section.file_offset as u64
+                && offset - (section.file_offset as u64) < section.size as u64
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Description, ElfNAddr, ElfNOff, ParsingError};
+use super::{layout, Description, ElfNAddr, ElfNOff, FieldSpan, ParsingError};
 use crate::utils::{self, RcSlice};
 
 const EI_NIDENT: usize = 16;
@@ -14,7 +14,7 @@ pub struct ElfHeader {
     pub e_entry: ElfNAddr,
     pub e_phoff: ElfNOff,
     pub e_shoff: ElfNOff,
-    pub e_flags: u32,
+    pub e_flags: EFlags,
     pub e_ehsize: u16,
     pub e_phentsize: u16,
     pub e_phnum: u16,
@@ -26,7 +26,7 @@ pub struct ElfHeader {
 impl ElfHeader {
     pub fn from(raw: RcSlice<u8>) -> Result<Self, ParsingError> {
         // extract e_ident
-        let e_ident = EIdent::from(RcSlice::from(&raw, 0, EI_NIDENT))?;
+        let mut e_ident = EIdent::from(RcSlice::from(&raw, 0, EI_NIDENT))?;
 
         // determine byte order
         let is_little_endian = match e_ident.ei_data.0 {
@@ -45,6 +45,10 @@ impl ElfHeader {
         // extract e_machine
         let e_machine = EMachine(raw.read_u16(18, is_little_endian));
 
+        // the meaning of some OS ABIs and of the ABI version depends on the machine
+        e_ident.ei_osabi.1 = e_machine.0;
+        e_ident.ei_abi_version.2 = e_machine.0;
+
         // extract e_version
         let e_version = raw.read_u32(20, is_little_endian);
 
@@ -81,7 +85,11 @@ impl ElfHeader {
             true => 48,
             false => 36,
         };
-        let e_flags = raw.read_u32(offset, is_little_endian);
+        let e_flags = EFlags(
+            raw.read_u32(offset, is_little_endian),
+            e_machine.0,
+            is_64_bit,
+        );
 
         // extract e_ehsize
         let offset = match is_64_bit {
@@ -153,6 +161,51 @@ impl ElfHeader {
     pub fn is_64_bit(&self) -> bool {
         self.is_64_bit
     }
+
+    /// File location of each field of the header (the fields of `e_ident` are given by `EIdent::spans`)
+    pub fn spans(&self) -> Vec<FieldSpan> {
+        let base = self.raw.start;
+        match self.is_64_bit {
+            true => layout(
+                base,
+                &[
+                    ("e_ident", 0, EI_NIDENT),
+                    ("e_type", 16, 2),
+                    ("e_machine", 18, 2),
+                    ("e_version", 20, 4),
+                    ("e_entry", 24, 8),
+                    ("e_phoff", 32, 8),
+                    ("e_shoff", 40, 8),
+                    ("e_flags", 48, 4),
+                    ("e_ehsize", 52, 2),
+                    ("e_phentsize", 54, 2),
+                    ("e_phnum", 56, 2),
+                    ("e_shentsize", 58, 2),
+                    ("e_shnum", 60, 2),
+                    ("e_shstrndx", 62, 2),
+                ],
+            ),
+            false => layout(
+                base,
+                &[
+                    ("e_ident", 0, EI_NIDENT),
+                    ("e_type", 16, 2),
+                    ("e_machine", 18, 2),
+                    ("e_version", 20, 4),
+                    ("e_entry", 24, 4),
+                    ("e_phoff", 28, 4),
+                    ("e_shoff", 32, 4),
+                    ("e_flags", 36, 4),
+                    ("e_ehsize", 40, 2),
+                    ("e_phentsize", 42, 2),
+                    ("e_phnum", 44, 2),
+                    ("e_shentsize", 46, 2),
+                    ("e_shnum", 48, 2),
+                    ("e_shstrndx", 50, 2),
+                ],
+            ),
+        }
+    }
 }
 
 pub struct EIdent {
@@ -165,11 +218,27 @@ pub struct EIdent {
     pub ei_data: EiData,
     pub ei_version: EiVersion,
     pub ei_osabi: EiOsAbi,
-    pub ei_abi_version: u8,
+    pub ei_abi_version: EiAbiVersion,
     pub ei_pad: RcSlice<u8>,
 }
 
 impl EIdent {
+    /// File location of each field of `e_ident`
+    pub fn spans(&self) -> Vec<FieldSpan> {
+        layout(
+            self.raw.start,
+            &[
+                ("EI_MAG", 0, 4),
+                ("EI_CLASS", 4, 1),
+                ("EI_DATA", 5, 1),
+                ("EI_VERSION", 6, 1),
+                ("EI_OSABI", 7, 1),
+                ("EI_ABIVERSION", 8, 1),
+                ("EI_PAD", 9, EI_NIDENT - 9),
+            ],
+        )
+    }
+
     pub fn from(raw: RcSlice<u8>) -> Result<Self, ParsingError> {
         let temp = raw.get();
 
@@ -191,8 +260,8 @@ impl EIdent {
         let ei_class = EiClass(temp[4]);
         let ei_data = EiData(temp[5]);
         let ei_version = EiVersion(temp[6]);
-        let ei_osabi = EiOsAbi(temp[7]);
-        let ei_abi_version = temp[8];
+        let ei_osabi = EiOsAbi(temp[7], 0);
+        let ei_abi_version = EiAbiVersion(temp[8], temp[7], 0);
         let ei_pad = RcSlice::from(&raw, 9, EI_NIDENT);
         Ok(Self {
             raw,
@@ -251,37 +320,70 @@ impl Description for EiVersion {
     }
 }
 
+/// OS ABI, along with the `e_machine` of the file which determines the meaning of values from 64 up
 #[derive(Debug, PartialEq)]
-pub struct EiOsAbi(pub u8);
+pub struct EiOsAbi(pub u8, pub u16);
 
 // https://github.com/eliben/pyelftools/blob/master/elftools/elf/enums.py#L35
 // https://github.com/eliben/pyelftools/blob/master/elftools/elf/descriptions.py#L308
 impl Description for EiOsAbi {
     fn to_str(&self) -> String {
-        match self.0 {
-            0 => String::from("UNIX - System V"),
-            1 => String::from("UNIX - HP-UX"),
-            2 => String::from("UNIX - NetBSD"),
-            3 => String::from("UNIX - Linux"),
-            4 => String::from("UNIX - GNU/Hurd"),
-            6 => String::from("UNIX - Solaris"),
-            7 => String::from("UNIX - AIX"),
-            8 => String::from("UNIX - IRIX"),
-            9 => String::from("UNIX - FreeBSD"),
-            10 => String::from("UNIX - TRU64"),
-            11 => String::from("Novell - Modesto"),
-            12 => String::from("UNIX - OpenBSD"),
-            13 => String::from("VMS - OpenVMS"),
-            14 => String::from("HP - Non-Stop Kernel"),
-            15 => String::from("AROS"),
-            16 => String::from("Fenix OS"),
-            17 => String::from("Nuxi - CloudABI"),
-            53 => String::from("Sortix"),
-            64 => String::from("ARM - EABI"),
-            97 => String::from("ARM - ABI"),
-            102 => String::from("CellOS Lv-2"),
-            255 => String::from("Standalone App"),
-            other => format!("<unknown: 0x{:x}>", other),
+        match (self.1, self.0) {
+            (_, 0) => String::from("UNIX - System V"),
+            (_, 1) => String::from("UNIX - HP-UX"),
+            (_, 2) => String::from("UNIX - NetBSD"),
+            (_, 3) => String::from("UNIX - Linux"),
+            (_, 4) => String::from("UNIX - GNU/Hurd"),
+            (_, 6) => String::from("UNIX - Solaris"),
+            (_, 7) => String::from("UNIX - AIX"),
+            (_, 8) => String::from("UNIX - IRIX"),
+            (_, 9) => String::from("UNIX - FreeBSD"),
+            (_, 10) => String::from("UNIX - TRU64"),
+            (_, 11) => String::from("Novell - Modesto"),
+            (_, 12) => String::from("UNIX - OpenBSD"),
+            (_, 13) => String::from("VMS - OpenVMS"),
+            (_, 14) => String::from("HP - Non-Stop Kernel"),
+            (_, 15) => String::from("AROS"),
+            (_, 16) => String::from("Fenix OS"),
+            (_, 17) => String::from("Nuxi - CloudABI"),
+            (_, 18) => String::from("Stratus Technologies OpenVOS"),
+            (_, 53) => String::from("Sortix"),
+            // AMDGPU
+            (224, 64) => String::from("AMD HSA Runtime"),
+            (224, 65) => String::from("AMD PAL Runtime"),
+            (224, 66) => String::from("AMD Mesa3D Runtime"),
+            // TI C6000
+            (140, 64) => String::from("Bare-metal C6000"),
+            (140, 65) => String::from("Linux C6000"),
+            (_, 64) => String::from("ARM - EABI"),
+            (_, 97) => String::from("ARM - ABI"),
+            (_, 102) => String::from("CellOS Lv-2"),
+            (_, 255) => String::from("Standalone App"),
+            (_, other @ 64..=254) => format!("<architecture specific: 0x{:x}>", other),
+            (_, other) => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+/// ABI version, along with the OS ABI and the `e_machine` of the file which determine its meaning
+#[derive(Debug, PartialEq)]
+pub struct EiAbiVersion(pub u8, pub u8, pub u16);
+
+impl Description for EiAbiVersion {
+    fn to_str(&self) -> String {
+        match (self.2, self.1, self.0) {
+            // the GNU dynamic linker rejects objects requiring ABI extensions it doesn't know
+            (_, 0 | 3, 0) => String::from("0 (no ABI extensions)"),
+            (_, 0 | 3, version) => format!(
+                "{} (requires a dynamic linker supporting ABI extension level {})",
+                version, version
+            ),
+            // AMDGPU HSA code object versions
+            (224, 64, version @ 0..=4) => format!("{} (HSA code object v{})", version, version + 2),
+            (224, 65 | 66, 0) => String::from("0"),
+            // the ARM EABI version is stored in e_flags
+            (40, 64, version) => format!("{} (EABI version is stored in e_flags)", version),
+            (_, _, version) => format!("{}", version),
         }
     }
 }
@@ -296,6 +398,8 @@ impl Description for EType {
             2 => "EXEC (Executable file)".to_owned(),
             3 => "DYN (Shared object file)".to_owned(),
             4 => "CORE (Core file)".to_owned(),
+            other @ 0xfe00..=0xfeff => format!("<OS specific: 0x{:x}>", other),
+            other @ 0xff00..=0xffff => format!("<processor specific: 0x{:x}>", other),
             other => format!("<unknown: 0x{:x}>", other),
         }
     }
@@ -312,24 +416,478 @@ impl Description for EMachine {
             3 => "Intel 80386".to_owned(),
             4 => "MC68000".to_owned(),
             5 => "MC88000".to_owned(),
+            6 => "Intel MCU".to_owned(),
             7 => "Intel 80860".to_owned(),
             8 => "MIPS R3000".to_owned(),
             9 => "IBM System/370".to_owned(),
+            10 => "MIPS R3000 little-endian".to_owned(),
             15 => "HPPA".to_owned(),
+            17 => "Fujitsu VPP500".to_owned(),
             18 => "Sparc v8+".to_owned(),
+            19 => "Intel 80960".to_owned(),
             20 => "PowerPC".to_owned(),
             21 => "PowerPC64".to_owned(),
             22 => "IBM S/390".to_owned(),
+            23 => "IBM SPU/SPC".to_owned(),
+            36 => "NEC V800".to_owned(),
+            37 => "Fujitsu FR20".to_owned(),
+            38 => "TRW RH-32".to_owned(),
+            39 => "Motorola RCE".to_owned(),
             40 => "ARM".to_owned(),
+            41 => "Digital Alpha (old)".to_owned(),
             42 => "Renesas / SuperH SH".to_owned(),
+            43 => "Sparc v9".to_owned(),
+            44 => "Siemens TriCore".to_owned(),
+            45 => "Argonaut RISC Core".to_owned(),
+            46 => "Renesas H8/300".to_owned(),
+            47 => "Renesas H8/300H".to_owned(),
+            48 => "Renesas H8S".to_owned(),
+            49 => "Renesas H8/500".to_owned(),
             50 => "Intel IA-64".to_owned(),
+            51 => "Stanford MIPS-X".to_owned(),
+            52 => "Motorola ColdFire".to_owned(),
+            53 => "Motorola M68HC12".to_owned(),
+            54 => "Fujitsu MMA Multimedia Accelerator".to_owned(),
+            55 => "Siemens PCP".to_owned(),
+            56 => "Sony nCPU embedded RISC processor".to_owned(),
+            57 => "Denso NDR1 microprocessor".to_owned(),
+            58 => "Motorola Star*Core processor".to_owned(),
+            59 => "Toyota ME16 processor".to_owned(),
+            60 => "STMicroelectronics ST100 processor".to_owned(),
+            61 => "Advanced Logic Corp. TinyJ embedded processor".to_owned(),
             62 => "Advanced Micro Devices X86-64".to_owned(),
+            63 => "Sony DSP processor".to_owned(),
+            64 => "Digital Equipment Corp. PDP-10".to_owned(),
+            65 => "Digital Equipment Corp. PDP-11".to_owned(),
+            66 => "Siemens FX66 microcontroller".to_owned(),
+            67 => "STMicroelectronics ST9+ 8/16 bit microcontroller".to_owned(),
+            68 => "STMicroelectronics ST7 8-bit microcontroller".to_owned(),
+            69 => "Motorola MC68HC16 Microcontroller".to_owned(),
+            70 => "Motorola MC68HC11 Microcontroller".to_owned(),
+            71 => "Motorola MC68HC08 Microcontroller".to_owned(),
+            72 => "Motorola MC68HC05 Microcontroller".to_owned(),
+            73 => "Silicon Graphics SVx".to_owned(),
+            74 => "STMicroelectronics ST19 8-bit microcontroller".to_owned(),
+            75 => "Digital VAX".to_owned(),
+            76 => "Axis Communications 32-bit embedded processor".to_owned(),
+            77 => "Infineon Technologies 32-bit embedded processor".to_owned(),
+            78 => "Element 14 64-bit DSP processor".to_owned(),
+            79 => "LSI Logic 16-bit DSP processor".to_owned(),
+            80 => "Donald Knuth's educational 64-bit processor".to_owned(),
+            81 => "Harvard University machine-independent object files".to_owned(),
+            82 => "SiTera Prism".to_owned(),
             83 => "Atmel AVR 8-bit microcontroller".to_owned(),
+            84 => "Fujitsu FR30".to_owned(),
+            85 => "Mitsubishi D10V".to_owned(),
+            86 => "Mitsubishi D30V".to_owned(),
+            87 => "NEC v850".to_owned(),
+            88 => "Mitsubishi M32R".to_owned(),
+            89 => "Matsushita MN10300".to_owned(),
+            90 => "Matsushita MN10200".to_owned(),
+            91 => "picoJava".to_owned(),
+            92 => "OpenRISC 32-bit embedded processor".to_owned(),
+            93 => "ARC International ARCompact processor".to_owned(),
+            94 => "Tensilica Xtensa Architecture".to_owned(),
+            95 => "Alphamosaic VideoCore processor".to_owned(),
+            96 => "Thompson Multimedia General Purpose Processor".to_owned(),
+            97 => "National Semiconductor 32000 series".to_owned(),
+            98 => "Tenor Network TPC processor".to_owned(),
+            99 => "Trebia SNP 1000 processor".to_owned(),
+            100 => "STMicroelectronics ST200 microcontroller".to_owned(),
+            101 => "Ubicom IP2xxx microcontroller family".to_owned(),
+            102 => "MAX Processor".to_owned(),
+            103 => "National Semiconductor CompactRISC microprocessor".to_owned(),
+            104 => "Fujitsu F2MC16".to_owned(),
+            105 => "Texas Instruments msp430 microcontroller".to_owned(),
             106 => "Analog Devices Blackfin".to_owned(),
+            107 => "Seiko Epson S1C33 family".to_owned(),
+            108 => "Sharp embedded microprocessor".to_owned(),
+            109 => "Arca RISC microprocessor".to_owned(),
+            110 => "PKU-Unity UniCore microprocessor".to_owned(),
+            111 => "eXcess 16/32/64-bit configurable embedded CPU".to_owned(),
+            112 => "Icera Semiconductor Deep Execution Processor".to_owned(),
+            113 => "Altera Nios II soft-core processor".to_owned(),
+            114 => "National Semiconductor CompactRISC CRX microprocessor".to_owned(),
+            115 => "Motorola XGATE embedded processor".to_owned(),
+            116 => "Infineon C16x/XC16x processor".to_owned(),
+            117 => "Renesas M16C series microprocessors".to_owned(),
+            118 => "Microchip Technology dsPIC30F Digital Signal Controller".to_owned(),
+            119 => "Freescale Communication Engine RISC core".to_owned(),
+            120 => "Renesas M32C series microprocessors".to_owned(),
+            131 => "Altium TSK3000 core".to_owned(),
+            132 => "Freescale RS08 embedded processor".to_owned(),
+            133 => "Analog Devices SHARC family of 32-bit DSP processors".to_owned(),
+            134 => "Cyan Technology eCOG2 microprocessor".to_owned(),
+            135 => "Sunplus S+core7 RISC processor".to_owned(),
+            136 => "New Japan Radio (NJR) 24-bit DSP processor".to_owned(),
+            137 => "Broadcom VideoCore III processor".to_owned(),
+            138 => "Lattice Mico32".to_owned(),
+            139 => "Seiko Epson C17 family".to_owned(),
+            140 => "Texas Instruments TMS320C6000 DSP family".to_owned(),
+            141 => "Texas Instruments TMS320C2000 DSP family".to_owned(),
+            142 => "Texas Instruments TMS320C55x DSP family".to_owned(),
+            143 => "Texas Instruments Application Specific RISC Processor".to_owned(),
+            144 => "Texas Instruments Programmable Realtime Unit".to_owned(),
+            160 => "STMicroelectronics 64-bit VLIW Data Signal Processor".to_owned(),
+            161 => "Cypress M8C microprocessor".to_owned(),
+            162 => "Renesas R32C series microprocessors".to_owned(),
+            163 => "NXP Semiconductors TriMedia architecture family".to_owned(),
+            164 => "Qualcomm Hexagon processor".to_owned(),
+            165 => "Intel 8051 and variants".to_owned(),
+            166 => "STMicroelectronics STxP7x family".to_owned(),
+            167 => "Andes Technology NDS32 embedded RISC processor family".to_owned(),
+            168 => "Cyan Technology eCOG1X family".to_owned(),
+            169 => "Dallas Semiconductor MAXQ30 core micro-controllers".to_owned(),
+            170 => "New Japan Radio (NJR) 16-bit DSP processor".to_owned(),
+            171 => "M2000 Reconfigurable RISC Microprocessor".to_owned(),
+            172 => "Cray Inc. NV2 vector architecture".to_owned(),
+            173 => "Renesas RX family".to_owned(),
+            174 => "Imagination Technologies META processor architecture".to_owned(),
+            175 => "MCST Elbrus general purpose hardware architecture".to_owned(),
+            176 => "Cyan Technology eCOG16 family".to_owned(),
+            177 => "National Semiconductor CompactRISC CR16 16-bit microprocessor".to_owned(),
+            178 => "Freescale Extended Time Processing Unit".to_owned(),
+            179 => "Infineon Technologies SLE9X core".to_owned(),
+            180 => "Intel L10M".to_owned(),
+            181 => "Intel K10M".to_owned(),
             183 => "AArch64".to_owned(),
+            185 => "Atmel Corporation 32-bit microprocessor family".to_owned(),
+            186 => "STMicroelectronics STM8 8-bit microcontroller".to_owned(),
+            187 => "Tilera TILE64 multicore architecture family".to_owned(),
+            188 => "Tilera TILEPro multicore architecture family".to_owned(),
+            189 => "Xilinx MicroBlaze 32-bit RISC soft processor core".to_owned(),
+            190 => "NVIDIA CUDA architecture".to_owned(),
+            191 => "Tilera TILE-Gx multicore architecture family".to_owned(),
+            192 => "CloudShield architecture family".to_owned(),
+            193 => "KIPO-KAIST Core-A 1st generation processor family".to_owned(),
+            194 => "KIPO-KAIST Core-A 2nd generation processor family".to_owned(),
+            195 => "Synopsys ARCv2".to_owned(),
+            196 => "Open8 8-bit RISC soft processor core".to_owned(),
+            197 => "Renesas RL78 family".to_owned(),
+            198 => "Broadcom VideoCore V processor".to_owned(),
+            199 => "Renesas 78KOR family".to_owned(),
+            200 => "Freescale 56800EX Digital Signal Controller".to_owned(),
+            201 => "Beyond BA1 CPU architecture".to_owned(),
+            202 => "Beyond BA2 CPU architecture".to_owned(),
+            203 => "XMOS xCORE processor family".to_owned(),
+            204 => "Microchip 8-bit PIC(r) family".to_owned(),
+            205 => "Intel Graphics Technology".to_owned(),
+            210 => "KM211 KM32 32-bit processor".to_owned(),
+            211 => "KM211 KMX32 32-bit processor".to_owned(),
+            212 => "KM211 KMX16 16-bit processor".to_owned(),
+            213 => "KM211 KMX8 8-bit processor".to_owned(),
+            214 => "KM211 KVARC processor".to_owned(),
+            215 => "Paneve CDP architecture family".to_owned(),
+            216 => "Cognitive Smart Memory Processor".to_owned(),
+            217 => "Bluechip Systems CoolEngine".to_owned(),
+            218 => "Nanoradio Optimized RISC".to_owned(),
+            219 => "CSR Kalimba architecture family".to_owned(),
+            220 => "Zilog Z80".to_owned(),
+            221 => "Controls and Data Services VISIUMcore processor".to_owned(),
+            222 => "FTDI Chip FT32 high performance 32-bit RISC architecture".to_owned(),
+            223 => "Moxie processor family".to_owned(),
+            224 => "AMD GPU architecture".to_owned(),
             243 => "RISC-V".to_owned(),
+            244 => "Lanai 32-bit processor".to_owned(),
+            245 => "CEVA Processor Architecture Family".to_owned(),
+            246 => "CEVA X2 Processor Family".to_owned(),
+            247 => "Linux BPF".to_owned(),
+            248 => "Graphcore Intelligent Processing Unit".to_owned(),
+            249 => "Imagination Technologies".to_owned(),
+            250 => "Netronome Flow Processor".to_owned(),
+            251 => "NEC Vector Engine".to_owned(),
+            252 => "C-SKY processor family".to_owned(),
+            253 => "Synopsys ARCv3 64-bit".to_owned(),
+            254 => "MOS Technology MCS 6502 processor".to_owned(),
+            255 => "Synopsys ARCv3 32-bit".to_owned(),
+            256 => "Kalray VLIW core of the MPPA processor family".to_owned(),
+            257 => "WDC 65816/65C816".to_owned(),
+            258 => "LoongArch".to_owned(),
+            259 => "ChipON KungFu32".to_owned(),
+            260 => "LAPIS nX-U16/U8".to_owned(),
+            261 => "Tachyum".to_owned(),
+            262 => "NXP 56800EF Digital Signal Controller".to_owned(),
+            0x1057 => "Atmel AVR 8-bit microcontroller (old)".to_owned(),
+            0x1059 => "Texas Instruments msp430 microcontroller (old)".to_owned(),
+            0x3330 => "Fujitsu FR30 (old)".to_owned(),
             0x9026 => "Alpha".to_owned(),
+            0x9041 => "Mitsubishi M32R (old)".to_owned(),
+            0x9080 => "Mitsubishi D10V (old)".to_owned(),
+            0xa390 => "IBM S/390 (old)".to_owned(),
+            0xbaab => "Xilinx MicroBlaze (old)".to_owned(),
             other => format!("<unknown: 0x{:x}>", other),
         }
     }
 }
+
+/// Processor specific flags, along with the `e_machine` and class of the file which determine their meaning
+pub struct EFlags(pub u32, pub u16, pub bool);
+
+impl EFlags {
+    // ARM
+    fn arm(flags: u32, known: &mut u32) -> Vec<String> {
+        let mut desc = Vec::new();
+        *known |= 0xff000000;
+        match flags >> 24 {
+            0 => {
+                desc.push(String::from("GNU EABI"));
+                let mut bit = |mask: u32, name: &str| {
+                    if flags & mask != 0 {
+                        *known |= mask;
+                        desc.push(name.to_owned());
+                    }
+                };
+                bit(0x1, "relocatable executable");
+                bit(0x2, "has entry point");
+                bit(0x4, "interworking enabled");
+                bit(0x20, "position independent");
+                bit(0x200, "software FP");
+                bit(0x400, "VFP");
+                bit(0x800, "Maverick FP");
+                *known |= 0x8;
+                desc.push(match flags & 0x8 != 0 {
+                    true => String::from("APCS-26"),
+                    false => String::from("APCS-32"),
+                });
+            }
+            version => {
+                desc.push(format!("Version{} EABI", version));
+                if version >= 4 {
+                    *known |= 0x00c00000;
+                    if flags & 0x00800000 != 0 {
+                        desc.push(String::from("BE8"));
+                    }
+                    if flags & 0x00400000 != 0 {
+                        desc.push(String::from("LE8"));
+                    }
+                }
+                if version >= 5 {
+                    *known |= 0x600;
+                    match flags & 0x600 {
+                        0x200 => desc.push(String::from("soft-float ABI")),
+                        0x400 => desc.push(String::from("hard-float ABI")),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        desc
+    }
+
+    // MIPS
+    fn mips(flags: u32, is_64_bit: bool, known: &mut u32) -> Vec<String> {
+        let mut desc = Vec::new();
+        *known |= 0xffff0000 | 0xf000 | 0x7bf;
+
+        let arch = match flags >> 28 {
+            0 => String::from("mips1"),
+            1 => String::from("mips2"),
+            2 => String::from("mips3"),
+            3 => String::from("mips4"),
+            4 => String::from("mips5"),
+            5 => String::from("mips32"),
+            6 => String::from("mips64"),
+            7 => String::from("mips32r2"),
+            8 => String::from("mips64r2"),
+            9 => String::from("mips32r6"),
+            10 => String::from("mips64r6"),
+            other => format!("<unknown arch: 0x{:x}>", other),
+        };
+        desc.push(arch);
+
+        let mach = match flags & 0x00ff0000 {
+            0 => None,
+            0x00810000 => Some(String::from("3900")),
+            0x00820000 => Some(String::from("4010")),
+            0x00830000 => Some(String::from("4100")),
+            0x00850000 => Some(String::from("4650")),
+            0x00870000 => Some(String::from("4120")),
+            0x00880000 => Some(String::from("4111")),
+            0x008a0000 => Some(String::from("sb1")),
+            0x008b0000 => Some(String::from("octeon")),
+            0x008c0000 => Some(String::from("xlr")),
+            0x008d0000 => Some(String::from("octeon2")),
+            0x008e0000 => Some(String::from("octeon3")),
+            0x00910000 => Some(String::from("5400")),
+            0x00920000 => Some(String::from("5900")),
+            0x00980000 => Some(String::from("5500")),
+            0x00990000 => Some(String::from("9000")),
+            0x00a00000 => Some(String::from("loongson-2e")),
+            0x00a10000 => Some(String::from("loongson-2f")),
+            0x00a20000 => Some(String::from("gs464")),
+            other => Some(format!("<unknown mach: 0x{:x}>", other)),
+        };
+        if let Some(mach) = mach {
+            desc.push(mach);
+        }
+
+        // without an explicit ABI the class and EF_MIPS_ABI2 determine it
+        let abi = match (flags & 0xf000, flags & 0x20 != 0, is_64_bit) {
+            (0x1000, _, _) => String::from("o32"),
+            (0x2000, _, _) => String::from("o64"),
+            (0x3000, _, _) => String::from("eabi32"),
+            (0x4000, _, _) => String::from("eabi64"),
+            (0, true, _) => String::from("n32"),
+            (0, false, true) => String::from("n64"),
+            (0, false, false) => String::from("o32"),
+            (other, _, _) => format!("<unknown ABI: 0x{:x}>", other),
+        };
+        desc.push(abi);
+
+        let mut bit = |mask: u32, name: &str| {
+            if flags & mask != 0 {
+                desc.push(name.to_owned());
+            }
+        };
+        bit(0x1, "noreorder");
+        bit(0x2, "pic");
+        bit(0x4, "cpic");
+        bit(0x8, "xgot");
+        bit(0x10, "ugen_reserved");
+        bit(0x80, "odk first");
+        bit(0x100, "32bitmode");
+        bit(0x200, "fp64");
+        bit(0x400, "nan2008");
+        bit(0x02000000, "micromips");
+        bit(0x04000000, "mips16");
+        bit(0x08000000, "mdmx");
+        desc
+    }
+
+    // RISC-V
+    fn riscv(flags: u32, known: &mut u32) -> Vec<String> {
+        let mut desc = Vec::new();
+        *known |= 0x1f;
+        if flags & 0x1 != 0 {
+            desc.push(String::from("RVC"));
+        }
+        desc.push(match (flags >> 1) & 0x3 {
+            0 => String::from("soft-float ABI"),
+            1 => String::from("single-float ABI"),
+            2 => String::from("double-float ABI"),
+            _ => String::from("quad-float ABI"),
+        });
+        if flags & 0x8 != 0 {
+            desc.push(String::from("RVE"));
+        }
+        if flags & 0x10 != 0 {
+            desc.push(String::from("TSO"));
+        }
+        desc
+    }
+
+    // PowerPC and PowerPC64
+    fn ppc(flags: u32, is_ppc64: bool, known: &mut u32) -> Vec<String> {
+        let mut desc = Vec::new();
+        match is_ppc64 {
+            true => {
+                *known |= 0x3;
+                desc.push(match flags & 0x3 {
+                    0 => String::from("unspecified ABI (ELFv1 assumed)"),
+                    1 => String::from("ELFv1 ABI"),
+                    2 => String::from("ELFv2 ABI"),
+                    other => format!("<unknown ABI: {}>", other),
+                });
+            }
+            false => {
+                *known |= 0x80018000;
+                if flags & 0x80000000 != 0 {
+                    desc.push(String::from("emb"));
+                }
+                if flags & 0x10000 != 0 {
+                    desc.push(String::from("relocatable"));
+                }
+                if flags & 0x8000 != 0 {
+                    desc.push(String::from("relocatable-lib"));
+                }
+            }
+        }
+        desc
+    }
+
+    // LoongArch
+    fn loongarch(flags: u32, known: &mut u32) -> Vec<String> {
+        *known |= 0xc7;
+        let float_abi = match flags & 0x7 {
+            1 => String::from("soft-float ABI"),
+            2 => String::from("single-float ABI"),
+            3 => String::from("double-float ABI"),
+            other => format!("<unknown float ABI: {}>", other),
+        };
+        let version = format!("object ABI v{}", (flags >> 6) & 0x3);
+        vec![float_abi, version]
+    }
+
+    // SPARC, SPARC32PLUS and SPARC V9
+    fn sparc(flags: u32, is_v9: bool, known: &mut u32) -> Vec<String> {
+        let mut desc = Vec::new();
+        *known |= 0xf00;
+        if is_v9 {
+            *known |= 0x3;
+            desc.push(match flags & 0x3 {
+                0 => String::from("tso"),
+                1 => String::from("pso"),
+                2 => String::from("rmo"),
+                _ => String::from("<unknown memory model>"),
+            });
+        }
+        let mut bit = |mask: u32, name: &str| {
+            if flags & mask != 0 {
+                desc.push(name.to_owned());
+            }
+        };
+        bit(0x100, "v8+");
+        bit(0x200, "ultrasparcI");
+        bit(0x400, "HaL R1");
+        bit(0x800, "ultrasparcIII");
+        desc
+    }
+
+    // AVR
+    fn avr(flags: u32, known: &mut u32) -> Vec<String> {
+        let mut desc = Vec::new();
+        *known |= 0xff;
+        desc.push(match flags & 0x7f {
+            1 => String::from("avr:1"),
+            2 => String::from("avr:2"),
+            3 => String::from("avr:3"),
+            4 => String::from("avr:4"),
+            5 => String::from("avr:5"),
+            6 => String::from("avr:6"),
+            25 => String::from("avr:25"),
+            31 => String::from("avr:31"),
+            35 => String::from("avr:35"),
+            51 => String::from("avr:51"),
+            100 => String::from("avr:100 (avrtiny)"),
+            other @ 101..=107 => format!("avr:{} (xmega{})", other, other - 100),
+            other => format!("<unknown mach: {}>", other),
+        });
+        if flags & 0x80 != 0 {
+            desc.push(String::from("link-relax"));
+        }
+        desc
+    }
+}
+
+impl Description for EFlags {
+    fn to_str(&self) -> String {
+        let flags = self.0;
+        let mut known = 0;
+        let mut desc = match self.1 {
+            2 | 18 => Self::sparc(flags, false, &mut known),
+            8 => Self::mips(flags, self.2, &mut known),
+            20 => Self::ppc(flags, false, &mut known),
+            21 => Self::ppc(flags, true, &mut known),
+            40 => Self::arm(flags, &mut known),
+            43 => Self::sparc(flags, true, &mut known),
+            83 => Self::avr(flags, &mut known),
+            243 => Self::riscv(flags, &mut known),
+            258 => Self::loongarch(flags, &mut known),
+            _ => Vec::new(),
+        };
+        if flags & !known != 0 {
+            desc.push(format!("<unknown: 0x{:x}>", flags & !known));
+        }
+        desc.join(", ")
+    }
+}
@@ -0,0 +1,106 @@
+use super::sections::Sections;
+use super::ProgramHeaderTable;
+
+/// Entropy (in bits per byte) above which data is most likely compressed or encrypted. Machine code stays well below
+pub const HIGH_ENTROPY: f64 = 7.2;
+
+/// Regions smaller than this have too few bytes for their entropy to mean anything
+pub const MIN_ENTROPY_SIZE: u64 = 512;
+
+/// Shannon entropy of the data, in bits per byte (0 to 8)
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for byte in data {
+        counts[*byte as usize] += 1;
+    }
+    let len = data.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count != 0)
+        .map(|count| {
+            let p = *count as f64 / len;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+/// Entropy of a range of bytes of the file
+pub struct RegionEntropy {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+    pub entropy: f64,
+    /// The region holds instructions (SHF_EXECINSTR, or a PT_LOAD segment with PF_X)
+    pub is_executable: bool,
+}
+
+impl RegionEntropy {
+    pub fn from(name: String, data: &[u8], offset: u64, is_executable: bool) -> Self {
+        Self {
+            name,
+            offset,
+            size: data.len() as u64,
+            entropy: shannon_entropy(data),
+            is_executable,
+        }
+    }
+
+    pub fn is_high(&self) -> bool {
+        self.size >= MIN_ENTROPY_SIZE && self.entropy >= HIGH_ENTROPY
+    }
+
+    /// Executable code with the entropy of compressed or encrypted data, as left by packers
+    pub fn is_likely_packed(&self) -> bool {
+        self.is_executable && self.is_high()
+    }
+}
+
+impl Sections {
+    /// Entropy of the file contents of every section (NOBITS sections have none)
+    pub fn entropy(&self) -> Vec<RegionEntropy> {
+        self.0
+            .iter()
+            .skip(1)
+            .filter(|section| !section.is_nobits())
+            .filter_map(|section| {
+                let name = match section.name.as_deref() {
+                    Some(name) if !name.is_empty() => name.to_owned(),
+                    _ => format!("Section {}", section.index),
+                };
+                let data = section.data()?;
+                Some(RegionEntropy::from(
+                    name,
+                    data,
+                    section.file_offset as u64,
+                    section.is_executable(),
+                ))
+            })
+            .collect()
+    }
+}
+
+impl ProgramHeaderTable {
+    /// Entropy of the file image of every segment, `data` being the whole file
+    pub fn entropy(&self, data: &[u8]) -> Vec<RegionEntropy> {
+        self.segments()
+            .iter()
+            .filter(|segment| segment.filesz != 0)
+            .map(|segment| {
+                let start = (segment.offset as usize).min(data.len());
+                let end = segment
+                    .offset
+                    .saturating_add(segment.filesz)
+                    .min(data.len() as u64) as usize;
+                RegionEntropy::from(
+                    format!("{:02} ({})", segment.index, segment.type_name()),
+                    &data[start..end],
+                    segment.offset,
+                    segment.is_executable(),
+                )
+            })
+            .collect()
+    }
+}
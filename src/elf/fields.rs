@@ -0,0 +1,446 @@
+/// Documentation of a single header field, so every frontend renders the same help text
+pub struct FieldInfo {
+    pub name: &'static str,
+    /// One line description
+    pub short: &'static str,
+    /// Detailed explanation of the field and how it is interpreted
+    pub long: &'static str,
+    /// Where the field is defined in the System V gABI
+    pub reference: &'static str,
+    /// Known values of the field (empty when the field holds a plain number, address or offset)
+    pub values: &'static [FieldValue],
+}
+
+pub struct FieldValue {
+    pub value: u64,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl FieldInfo {
+    pub fn value(&self, value: u64) -> Option<&FieldValue> {
+        self.values.iter().find(|v| v.value == value)
+    }
+
+    /// Render the complete documentation of the field as plain text
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}: {}\n\n{}\n", self.name, self.short, self.long);
+        if !self.values.is_empty() {
+            text.push_str("\nKnown values:\n");
+            for value in self.values {
+                text.push_str(&format!(
+                    "  0x{:x} {} - {}\n",
+                    value.value, value.name, value.description
+                ));
+            }
+        }
+        text.push_str(&format!("\nReference: {}\n", self.reference));
+        text
+    }
+}
+
+/// Find a field by name in one of the field lists below
+pub fn field<'a>(fields: &'a [FieldInfo], name: &str) -> Option<&'a FieldInfo> {
+    fields.iter().find(|field| field.name == name)
+}
+
+macro_rules! values {
+    ($(($value:expr, $name:expr, $description:expr)),* $(,)?) => {
+        &[$(FieldValue { value: $value, name: $name, description: $description }),*]
+    };
+}
+
+const GABI_EHDR: &str = "System V gABI, chapter 4 (Object Files), ELF Header";
+const GABI_EIDENT: &str = "System V gABI, chapter 4 (Object Files), ELF Identification";
+const GABI_SHDR: &str = "System V gABI, chapter 4 (Object Files), Sections";
+const GABI_PHDR: &str = "System V gABI, chapter 5 (Program Loading), Program Header";
+
+pub const ELF_HEADER_DESCRIPTION: &str = "The ELF header contains general information as well as the locations of the program and section header tables";
+pub const PROGRAM_HEADERS_DESCRIPTION: &str = "Program headers contain segments which describe the memory layout of the program and are necessary for loading it";
+pub const SECTION_HEADERS_DESCRIPTION: &str =
+    "Section headers contain linking and debugging information";
+
+/// Fields of `e_ident`, in file order
+pub const EIDENT_FIELDS: &[FieldInfo] = &[
+    FieldInfo {
+        name: "EI_MAG",
+        short: "ELF magic (should be 0x7f ELF)",
+        long: "The first four bytes of the file identify it as an ELF object file and must be 0x7f, 'E', 'L', 'F'.",
+        reference: GABI_EIDENT,
+        values: &[],
+    },
+    FieldInfo {
+        name: "EI_CLASS",
+        short: "ELF class (32/64 bit)",
+        long: "Identifies the file's class, which determines the size of addresses and offsets and the layout of all other headers.",
+        reference: GABI_EIDENT,
+        values: values![
+            (0, "ELFCLASSNONE", "Invalid class"),
+            (1, "ELFCLASS32", "32-bit objects"),
+            (2, "ELFCLASS64", "64-bit objects"),
+        ],
+    },
+    FieldInfo {
+        name: "EI_DATA",
+        short: "Data format (little/big endian)",
+        long: "Specifies the encoding of processor specific data in the file, which applies to every multi-byte field.",
+        reference: GABI_EIDENT,
+        values: values![
+            (0, "ELFDATANONE", "Invalid data encoding"),
+            (1, "ELFDATA2LSB", "Two's complement, little endian"),
+            (2, "ELFDATA2MSB", "Two's complement, big endian"),
+        ],
+    },
+    FieldInfo {
+        name: "EI_VERSION",
+        short: "ELF identifier version",
+        long: "The ELF header version number, which must be EV_CURRENT.",
+        reference: GABI_EIDENT,
+        values: values![
+            (0, "EV_NONE", "Invalid version"),
+            (1, "EV_CURRENT", "Current version"),
+        ],
+    },
+    FieldInfo {
+        name: "EI_OSABI",
+        short: "Operating system ABI",
+        long: "Identifies the OS or ABI specific extensions used by the file. Values from 64 up are architecture specific, so their meaning depends on e_machine.",
+        reference: GABI_EIDENT,
+        values: values![
+            (0, "ELFOSABI_NONE", "No extensions or unspecified (UNIX System V)"),
+            (1, "ELFOSABI_HPUX", "Hewlett-Packard HP-UX"),
+            (2, "ELFOSABI_NETBSD", "NetBSD"),
+            (3, "ELFOSABI_GNU", "GNU (Linux), set when GNU extensions such as IFUNC or unique symbols are used"),
+            (6, "ELFOSABI_SOLARIS", "Sun Solaris"),
+            (7, "ELFOSABI_AIX", "AIX"),
+            (8, "ELFOSABI_IRIX", "IRIX"),
+            (9, "ELFOSABI_FREEBSD", "FreeBSD"),
+            (10, "ELFOSABI_TRU64", "Compaq TRU64 UNIX"),
+            (11, "ELFOSABI_MODESTO", "Novell Modesto"),
+            (12, "ELFOSABI_OPENBSD", "Open BSD"),
+            (13, "ELFOSABI_OPENVMS", "Open VMS"),
+            (14, "ELFOSABI_NSK", "Hewlett-Packard Non-Stop Kernel"),
+            (15, "ELFOSABI_AROS", "Amiga Research OS"),
+            (16, "ELFOSABI_FENIXOS", "FenixOS"),
+            (17, "ELFOSABI_CLOUDABI", "Nuxi CloudABI"),
+            (18, "ELFOSABI_OPENVOS", "Stratus Technologies OpenVOS"),
+        ],
+    },
+    FieldInfo {
+        name: "EI_ABIVERSION",
+        short: "ABI version",
+        long: "Identifies the version of the ABI given by EI_OSABI the file targets. Its interpretation depends on the OS ABI, and it should be 0 when no versions are defined.",
+        reference: GABI_EIDENT,
+        values: &[],
+    },
+    FieldInfo {
+        name: "EI_PAD",
+        short: "Pad (should be all zeroes)",
+        long: "Marks the beginning of the unused bytes in e_ident, which are reserved and should be set to zero.",
+        reference: GABI_EIDENT,
+        values: &[],
+    },
+];
+
+/// Fields of the ELF header, in file order
+pub const ELF_HEADER_FIELDS: &[FieldInfo] = &[
+    FieldInfo {
+        name: "e_ident",
+        short: "ELF identifier (inner fields displayed below)",
+        long: "Machine independent bytes which mark the file as an ELF object and describe how to decode the rest of it.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_type",
+        short: "ELF type",
+        long: "Identifies the object file type. Values from ET_LOOS to ET_HIOS are OS specific and values from ET_LOPROC to ET_HIPROC are processor specific.",
+        reference: GABI_EHDR,
+        values: values![
+            (0, "ET_NONE", "No file type"),
+            (1, "ET_REL", "Relocatable file"),
+            (2, "ET_EXEC", "Executable file"),
+            (3, "ET_DYN", "Shared object file (including position independent executables)"),
+            (4, "ET_CORE", "Core file"),
+        ],
+    },
+    FieldInfo {
+        name: "e_machine",
+        short: "Target CPU architecture",
+        long: "Specifies the required architecture of the file. It also determines the meaning of processor specific values in other fields, such as e_flags, section types and relocation types.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_version",
+        short: "ELF version",
+        long: "Identifies the object file version, which must be EV_CURRENT.",
+        reference: GABI_EHDR,
+        values: values![
+            (0, "EV_NONE", "Invalid version"),
+            (1, "EV_CURRENT", "Current version"),
+        ],
+    },
+    FieldInfo {
+        name: "e_entry",
+        short: "Entry point (memory address)",
+        long: "The virtual address to which the system first transfers control when starting the process. It is 0 when the file has no associated entry point.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_phoff",
+        short: "Program header table file offset",
+        long: "The file offset of the program header table in bytes. It is 0 when the file has no program header table.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_shoff",
+        short: "Section header table file offset",
+        long: "The file offset of the section header table in bytes. It is 0 when the file has no section header table.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_flags",
+        short: "Processor specific flags",
+        long: "Processor specific flags associated with the file, such as the ABI variant, floating point conventions or ISA level. Their meaning depends on e_machine.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_ehsize",
+        short: "Size of the ELF header",
+        long: "The size of the ELF header in bytes (52 for 32-bit files and 64 for 64-bit files).",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_phentsize",
+        short: "Size of each program header",
+        long: "The size in bytes of one entry in the program header table. All entries are the same size.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_phnum",
+        short: "Number of program headers",
+        long: "The number of entries in the program header table. If the number is PN_XNUM (0xffff) or greater, the real number is held in the sh_info field of the initial section header.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_shentsize",
+        short: "Size of each section header",
+        long: "The size in bytes of one entry in the section header table. All entries are the same size.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_shnum",
+        short: "Number of section headers",
+        long: "The number of entries in the section header table. If the number is SHN_LORESERVE (0xff00) or greater, this field is 0 and the real number is held in the sh_size field of the initial section header.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "e_shstrndx",
+        short: "Section header table index of the entry that contains the section names",
+        long: "The section header table index of the section name string table, or SHN_UNDEF if there is none. If the index is SHN_LORESERVE (0xff00) or greater, this field is SHN_XINDEX (0xffff) and the real index is held in the sh_link field of the initial section header.",
+        reference: GABI_EHDR,
+        values: &[],
+    },
+];
+
+/// Fields of a program header, in the order of the 32-bit structure
+pub const PROGRAM_HEADER_FIELDS: &[FieldInfo] = &[
+    FieldInfo {
+        name: "p_type",
+        short: "Program header type",
+        long: "Specifies what kind of segment the header describes, or how to interpret its information.",
+        reference: GABI_PHDR,
+        values: values![
+            (0, "PT_NULL", "NULL headers should be ignored"),
+            (1, "PT_LOAD", "LOAD headers specify a segment of the program that should be loaded into memory"),
+            (2, "PT_DYNAMIC", "DYNAMIC headers specify information for the dynamic linker"),
+            (3, "PT_INTERP", "INTERP headers specify the filesystem path of the dynamic linker"),
+            (4, "PT_NOTE", "NOTE headers contain system specific information used for various purposes"),
+            (5, "PT_SHLIB", "SHLIB headers are reserved and currently save no purpose"),
+            (6, "PT_PHDR", "A PHDR header specifies the location of the program header table itself in the file and in memory, if it is to be loaded into memory"),
+            (7, "PT_TLS", "TLS headers specify a thread-local storage section"),
+            (0x6474e550, "PT_GNU_EH_FRAME", "GNU_EH_FRAME headers specify exception handler information for GCC"),
+            (0x6474e551, "PT_GNU_STACK", "GNU_STACK headers specify whether the stack should have execute permissions"),
+            (0x6474e552, "PT_GNU_RELRO", "GNU_RELRO headers spceify which parts of memory should be marked as read-only after relocation"),
+            (0x6474e553, "PT_GNU_PROPERTY", "GNU_PROPERTY headers specify special handling requirements for the kernel and dynamic linker"),
+        ],
+    },
+    FieldInfo {
+        name: "p_offset",
+        short: "Segment offset into the file",
+        long: "The offset from the beginning of the file at which the first byte of the segment resides.",
+        reference: GABI_PHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "p_vaddr",
+        short: "Segment loading address (virtual)",
+        long: "The virtual address at which the first byte of the segment resides in memory.",
+        reference: GABI_PHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "p_paddr",
+        short: "Segment loading address (physical)",
+        long: "The segment's physical address, on systems for which physical addressing is relevant. It is usually ignored for executables and shared objects.",
+        reference: GABI_PHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "p_filesz",
+        short: "Segment size in the file",
+        long: "The number of bytes in the file image of the segment, which may be 0.",
+        reference: GABI_PHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "p_memsz",
+        short: "Segment size in memory",
+        long: "The number of bytes in the memory image of the segment, which may be 0. Bytes beyond p_filesz are zero filled (e.g. .bss).",
+        reference: GABI_PHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "p_flags",
+        short: "Segment permissions (R=Read, W=Write, E=Execute)",
+        long: "Flags relevant to the segment, which give the memory permissions of a loaded segment.",
+        reference: GABI_PHDR,
+        values: values![
+            (0x1, "PF_X", "Execute"),
+            (0x2, "PF_W", "Write"),
+            (0x4, "PF_R", "Read"),
+        ],
+    },
+    FieldInfo {
+        name: "p_align",
+        short: "Alignment in memory",
+        long: "The value to which the segment is aligned in memory and in the file. p_vaddr should equal p_offset modulo p_align, and values 0 and 1 mean no alignment is required.",
+        reference: GABI_PHDR,
+        values: &[],
+    },
+];
+
+/// Fields of a section header, in file order
+pub const SECTION_HEADER_FIELDS: &[FieldInfo] = &[
+    FieldInfo {
+        name: "sh_name",
+        short: "Section name",
+        long: "The name of the section, given as an index into the section header string table.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_type",
+        short: "Section type",
+        long: "Categorizes the section's contents and semantics. Values from SHT_LOOS to SHT_HIOS are OS specific and values from SHT_LOPROC to SHT_HIPROC are processor specific.",
+        reference: GABI_SHDR,
+        values: values![
+            (0, "SHT_NULL", "NULL represents an entry with no associated section"),
+            (1, "SHT_PROGBITS", "PROGBITS sections contain information defined by the program"),
+            (2, "SHT_SYMTAB", "SYMTAB sections hold a symbol table"),
+            (3, "SHT_STRTAB", "STRTAB sections hold a string table"),
+            (4, "SHT_RELA", "RELA sections hold relocation entries with explicit addends"),
+            (5, "SHT_HASH", "HASH sections hold a symbol hash table, which is required for dynamically linked files"),
+            (6, "SHT_DYNAMIC", "DYNAMIC sections contain information for dynamic linking"),
+            (7, "SHT_NOTE", "NOTE sections contain special information that marks the file in some way"),
+            (8, "SHT_NOBITS", "NOBITS sections are similar to PROGBITS sections, but occupy no space in the file"),
+            (9, "SHT_REL", "REL sections hold relocation entries without explicit addends"),
+            (10, "SHT_SHLIB", "SHLIB identify reserved sections"),
+            (11, "SHT_DYNSYM", "DYNSYM sections hold a minimal symbol table"),
+            (14, "SHT_INIT_ARRAY", "INIT_ARRAY sections hold a set of pointers to initialization functions"),
+            (15, "SHT_FINI_ARRAY", "FINI_ARRAY sections hold a set of pointers to termination functions"),
+            (16, "SHT_PREINIT_ARRAY", "PREINIT_ARRAY sections hold a set of pointers to functions that are invoked before all other initialization functions"),
+            (17, "SHT_GROUP", "GROUP sections define a set of sections that must be treated as a unit by the linker"),
+            (18, "SHT_SYMTAB_SHNDX", "SYMTAB_SHNDX sections hold extended section indexes of the symbols in the associated symbol table"),
+            (19, "SHT_RELR", "RELR sections hold relative relocations in a compact address and bitmap encoding"),
+            (0x60000001, "SHT_ANDROID_REL", "ANDROID_REL and ANDROID_RELA sections hold relocation entries in Android's packed (APS2) encoding"),
+            (0x60000002, "SHT_ANDROID_RELA", "ANDROID_REL and ANDROID_RELA sections hold relocation entries in Android's packed (APS2) encoding"),
+            (0x6fffff00, "SHT_ANDROID_RELR", "ANDROID_RELR sections hold relative relocations in a compact address and bitmap encoding"),
+            (0x6ffffff6, "SHT_GNU_HASH", "GNU_HASH sections hold a GNU style symbol hash table"),
+            (0x6ffffffd, "SHT_GNU_verdef", "VERDEF sections contain versioning information"),
+            (0x6ffffffe, "SHT_GNU_verneed", "VERNEED sections contain dependency information"),
+            (0x6fffffff, "SHT_GNU_versym", "VERSYM sections describe the relation between symbols and version information"),
+        ],
+    },
+    FieldInfo {
+        name: "sh_flags",
+        short: "Section flags (W=Write, A=Alloc, X=Execute, M=Merge, S=Strings, I=Info Link, L=Link Order, O=OS Nonconforming, G=Group, T=TLS, C=Compressed, E=Exclude, R=Retain, D=Mbind, l=Large, y=Purecode, v=VLE, o=OS specific, p=Processor specific, x=Unknown)",
+        long: "One-bit flags describing miscellaneous attributes of the section. Bits in SHF_MASKOS are OS specific and bits in SHF_MASKPROC are processor specific, so their meaning depends on e_machine.",
+        reference: GABI_SHDR,
+        values: values![
+            (0x1, "SHF_WRITE", "Contains data that should be writable during process execution"),
+            (0x2, "SHF_ALLOC", "Occupies memory during process execution"),
+            (0x4, "SHF_EXECINSTR", "Contains executable machine instructions"),
+            (0x10, "SHF_MERGE", "Data may be merged to eliminate duplication"),
+            (0x20, "SHF_STRINGS", "Consists of null-terminated strings"),
+            (0x40, "SHF_INFO_LINK", "sh_info holds a section header table index"),
+            (0x80, "SHF_LINK_ORDER", "Adds special ordering requirements for link editors"),
+            (0x100, "SHF_OS_NONCONFORMING", "Requires special OS specific processing"),
+            (0x200, "SHF_GROUP", "Member of a section group"),
+            (0x400, "SHF_TLS", "Holds thread-local storage"),
+            (0x800, "SHF_COMPRESSED", "Holds compressed data"),
+            (0x200000, "SHF_GNU_RETAIN", "Must not be garbage collected by the linker"),
+            (0x1000000, "SHF_GNU_MBIND", "Placed in a special memory region"),
+            (0x80000000, "SHF_EXCLUDE", "Excluded from executables and shared objects"),
+        ],
+    },
+    FieldInfo {
+        name: "sh_addr",
+        short: "Section address in memory",
+        long: "If the section appears in the memory image of a process, the address at which its first byte should reside. Otherwise 0.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_offset",
+        short: "Section offset in file",
+        long: "The offset from the beginning of the file to the first byte of the section. NOBITS sections occupy no space in the file, and their offset is only conceptual.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_size",
+        short: "Section size",
+        long: "The section's size in bytes. A NOBITS section may have a non-zero size, but it occupies no space in the file.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_link",
+        short: "Section header table link (interpretation depends on section type)",
+        long: "A section header table index link, such as the string table of a symbol table or the symbol table of a relocation section.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_info",
+        short: "Extra info (interpretation depends on section type)",
+        long: "Extra information, such as the section a relocation section applies to, or one greater than the index of the last local symbol of a symbol table.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_addralign",
+        short: "Address alignment of section",
+        long: "Alignment constraint of the section: sh_addr must be congruent to 0 modulo this value. Values 0 and 1 mean the section has no alignment constraints.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+    FieldInfo {
+        name: "sh_entsize",
+        short: "Entry size (for sections that hold a table of some sort)",
+        long: "The size in bytes of each entry, for sections that hold a table of fixed-size entries such as a symbol table. 0 if the section doesn't hold such a table.",
+        reference: GABI_SHDR,
+        values: &[],
+    },
+];
@@ -0,0 +1,141 @@
+use super::sections::Section;
+use super::{Elf, Segment};
+use crate::utils::Table;
+
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PT_NOTE: u32 = 4;
+const PT_PHDR: u32 = 6;
+const PT_TLS: u32 = 7;
+const PT_GNU_EH_FRAME: u32 = 0x6474e550;
+const PT_GNU_STACK: u32 = 0x6474e551;
+const PT_GNU_RELRO: u32 = 0x6474e552;
+const PT_GNU_SFRAME: u32 = 0x6474e554;
+const PT_GNU_MBIND_LO: u32 = 0x6474e555;
+const PT_GNU_MBIND_HI: u32 = 0x6474f554;
+
+/// Whether a section lies inside a segment, following the rules used by binutils (`ELF_SECTION_IN_SEGMENT_STRICT`)
+pub fn section_in_segment(section: &Section, segment: &Segment) -> bool {
+    let p_type = segment.p_type;
+    let is_tls = section.is_tls();
+    let is_alloc = section.is_alloc();
+    let is_nobits = section.is_nobits();
+    let offset = section.file_offset as u64;
+    let addr = section.addr;
+
+    // .tbss only occupies memory in the TLS segment, its addresses overlap the following sections
+    if is_tls && is_nobits && p_type != PT_TLS {
+        return false;
+    }
+    let size = section.size as u64;
+
+    // only PT_LOAD, PT_GNU_RELRO and PT_TLS contain TLS sections, PT_TLS contains only TLS sections and PT_PHDR none
+    let tls_ok = match is_tls {
+        true => matches!(p_type, PT_TLS | PT_GNU_RELRO | PT_LOAD),
+        false => p_type != PT_TLS && p_type != PT_PHDR,
+    };
+    if !tls_ok {
+        return false;
+    }
+
+    // segments which are loaded only contain SHF_ALLOC sections
+    let loaded = matches!(
+        p_type,
+        PT_LOAD | PT_DYNAMIC | PT_GNU_EH_FRAME | PT_GNU_STACK | PT_GNU_RELRO | PT_GNU_SFRAME
+    ) || (PT_GNU_MBIND_LO..=PT_GNU_MBIND_HI).contains(&p_type);
+    if !is_alloc && loaded {
+        return false;
+    }
+
+    // sections with contents must lie within the file image of the segment
+    if !is_nobits
+        && (offset < segment.offset
+            || offset - segment.offset > segment.filesz.wrapping_sub(1)
+            || offset - segment.offset + size > segment.filesz)
+    {
+        return false;
+    }
+
+    // allocated sections must lie within the memory image of the segment
+    if is_alloc
+        && (addr < segment.vaddr
+            || addr - segment.vaddr > segment.memsz.wrapping_sub(1)
+            || addr - segment.vaddr + size > segment.memsz)
+    {
+        return false;
+    }
+
+    // zero size sections at the start or end of PT_DYNAMIC and PT_NOTE don't belong to them
+    if (p_type == PT_DYNAMIC || p_type == PT_NOTE) && size == 0 && segment.memsz != 0 {
+        let inside_file =
+            is_nobits || (offset > segment.offset && offset - segment.offset < segment.filesz);
+        let inside_memory =
+            !is_alloc || (addr > segment.vaddr && addr - segment.vaddr < segment.memsz);
+        return inside_file && inside_memory;
+    }
+    true
+}
+
+impl Elf {
+    /// Indexes of the sections contained in a segment
+    pub fn segment_sections(&self, segment: &Segment) -> Vec<usize> {
+        self.sections
+            .0
+            .iter()
+            .skip(1)
+            .filter(|section| section_in_segment(section, segment))
+            .map(|section| section.index)
+            .collect()
+    }
+
+    /// Indexes of the program headers whose segments contain a section
+    pub fn section_segments(&self, section: &Section) -> Vec<usize> {
+        match section.index {
+            0 => Vec::new(),
+            _ => self
+                .phdr_table
+                .segments()
+                .iter()
+                .filter(|segment| section_in_segment(section, segment))
+                .map(|segment| segment.index)
+                .collect(),
+        }
+    }
+
+    /// Section to segment mapping, as printed by `readelf -l`
+    pub fn segment_mapping_table(&self) -> Table {
+        let mut table = Table::new(&["Segment", "Type", "Sections"]);
+        for segment in self.phdr_table.segments() {
+            let names: Vec<&str> = self
+                .segment_sections(&segment)
+                .into_iter()
+                .map(|index| self.sections.0[index].name.as_deref().unwrap_or(""))
+                .collect();
+            table.push(vec![
+                format!("{:02}", segment.index),
+                segment.type_name(),
+                names.join(" "),
+            ]);
+        }
+        table
+    }
+
+    /// Segment to section mapping, listing the segments containing each section
+    pub fn section_mapping_table(&self) -> Table {
+        let mut table = Table::new(&["Section", "Name", "Segments"]);
+        let segments = self.phdr_table.segments();
+        for section in self.sections.0.iter().skip(1) {
+            let indexes: Vec<String> = self
+                .section_segments(section)
+                .into_iter()
+                .map(|index| format!("{:02} ({})", index, segments[index].type_name()))
+                .collect();
+            table.push(vec![
+                format!("{}", section.index),
+                section.name.clone().unwrap_or_default(),
+                indexes.join(", "),
+            ]);
+        }
+        table
+    }
+}
@@ -1,13 +1,25 @@
 use crate::utils::RcSlice;
 use std::rc::Rc;
+mod address;
+pub use address::*;
 mod elf_header;
 pub use elf_header::*;
+mod entropy;
+pub use entropy::*;
+mod fields;
+pub use fields::*;
+mod mapping;
+pub use mapping::*;
 mod program_headers;
 pub use program_headers::*;
 mod section_headers;
 pub use section_headers::*;
 pub mod sections;
+mod span;
 use sections::Sections;
+pub use span::*;
+mod symbolize;
+pub use symbolize::*;
 
 const ELF_HDR_MAX_SIZE: usize = 64;
 
@@ -18,10 +30,12 @@ pub trait Description {
 pub struct Elf {
     is_little_endian: bool,
     is_64_bit: bool,
+    pub raw: RcSlice<u8>,
     pub hdr: ElfHeader,
     pub phdr_table: ProgramHeaderTable,
     pub shdr_table: SectionHeaderTable,
     pub sections: Sections,
+    pub symbol_index: SymbolIndex,
 }
 
 impl Elf {
@@ -38,14 +52,17 @@ impl Elf {
         shdr_table.populate_names(RcSlice::from(&raw, 0, len), &hdr);
 
         let sections = Sections::from(raw.clone(), &hdr, &shdr_table);
+        let symbol_index = SymbolIndex::from(&sections);
 
         Ok(Self {
             is_little_endian,
             is_64_bit,
+            raw,
             hdr,
             phdr_table,
             shdr_table,
             sections,
+            symbol_index,
         })
     }
 
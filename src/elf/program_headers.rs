@@ -1,4 +1,4 @@
-use super::{Description, ElfHeader, ElfNAddr, ElfNOff};
+use super::{layout, Description, ElfHeader, ElfNAddr, ElfNOff, FieldSpan, Span};
 use crate::utils::RcSlice;
 
 pub struct ProgramHeaderTable {
@@ -20,6 +20,42 @@ impl ProgramHeaderTable {
         }
     }
 
+    /// Class independent view of the program headers
+    pub fn segments(&self) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        if let Some(phdrs) = &self.phdrs32 {
+            for (index, phdr) in phdrs.iter().enumerate() {
+                segments.push(Segment {
+                    index,
+                    header: Span::of(&phdr.raw),
+                    p_type: phdr.p_type.0,
+                    p_flags: phdr.p_flags.0,
+                    offset: phdr.p_offset.to_u64(),
+                    vaddr: phdr.p_vaddr.to_u64(),
+                    filesz: phdr.p_filesz as u64,
+                    memsz: phdr.p_memsz as u64,
+                    align: phdr.p_align as u64,
+                });
+            }
+        }
+        if let Some(phdrs) = &self.phdrs64 {
+            for (index, phdr) in phdrs.iter().enumerate() {
+                segments.push(Segment {
+                    index,
+                    header: Span::of(&phdr.raw),
+                    p_type: phdr.p_type.0,
+                    p_flags: phdr.p_flags.0,
+                    offset: phdr.p_offset.to_u64(),
+                    vaddr: phdr.p_vaddr.to_u64(),
+                    filesz: phdr.p_filesz,
+                    memsz: phdr.p_memsz,
+                    align: phdr.p_align,
+                });
+            }
+        }
+        segments
+    }
+
     fn from_32_bit(raw: RcSlice<u8>, hdr: &ElfHeader) -> Vec<ProgramHeader32> {
         let is_little_endian = hdr.is_little_endian();
         let is_64_bit = false;
@@ -42,6 +78,7 @@ impl ProgramHeaderTable {
             let p_flags = PFlags(temp.read_u32(24, is_little_endian));
             let p_align = temp.read_u32(28, is_little_endian);
             phdrs.push(ProgramHeader32 {
+                raw: temp.clone(),
                 p_type,
                 p_offset,
                 p_vaddr,
@@ -77,6 +114,7 @@ impl ProgramHeaderTable {
             let p_memsz = temp.read_u64(40, is_little_endian);
             let p_align = temp.read_u64(48, is_little_endian);
             phdrs.push(ProgramHeader64 {
+                raw: temp.clone(),
                 p_type,
                 p_flags,
                 p_offset,
@@ -92,6 +130,7 @@ impl ProgramHeaderTable {
 }
 
 pub struct ProgramHeader32 {
+    pub raw: RcSlice<u8>,
     pub p_type: PType,
     pub p_offset: ElfNOff,
     pub p_vaddr: ElfNAddr,
@@ -103,6 +142,7 @@ pub struct ProgramHeader32 {
 }
 
 pub struct ProgramHeader64 {
+    pub raw: RcSlice<u8>,
     pub p_type: PType,
     pub p_flags: PFlags,
     pub p_offset: ElfNOff,
@@ -113,6 +153,68 @@ pub struct ProgramHeader64 {
     pub p_align: u64,
 }
 
+impl ProgramHeader32 {
+    /// File location of each field of the header
+    pub fn spans(&self) -> Vec<FieldSpan> {
+        layout(
+            self.raw.start,
+            &[
+                ("p_type", 0, 4),
+                ("p_offset", 4, 4),
+                ("p_vaddr", 8, 4),
+                ("p_paddr", 12, 4),
+                ("p_filesz", 16, 4),
+                ("p_memsz", 20, 4),
+                ("p_flags", 24, 4),
+                ("p_align", 28, 4),
+            ],
+        )
+    }
+}
+
+impl ProgramHeader64 {
+    /// File location of each field of the header
+    pub fn spans(&self) -> Vec<FieldSpan> {
+        layout(
+            self.raw.start,
+            &[
+                ("p_type", 0, 4),
+                ("p_flags", 4, 4),
+                ("p_offset", 8, 8),
+                ("p_vaddr", 16, 8),
+                ("p_paddr", 24, 8),
+                ("p_filesz", 32, 8),
+                ("p_memsz", 40, 8),
+                ("p_align", 48, 8),
+            ],
+        )
+    }
+}
+
+pub struct Segment {
+    pub index: usize,
+    /// Location of the program header describing the segment
+    pub header: Span,
+    pub p_type: u32,
+    pub p_flags: u32,
+    pub offset: u64,
+    pub vaddr: u64,
+    pub filesz: u64,
+    pub memsz: u64,
+    pub align: u64,
+}
+
+impl Segment {
+    pub fn type_name(&self) -> String {
+        PType(self.p_type).to_str()
+    }
+
+    /// Whether the segment is loaded with execute permission (PT_LOAD with PF_X)
+    pub fn is_executable(&self) -> bool {
+        self.p_type == 1 && self.p_flags & 0x1 != 0
+    }
+}
+
 pub struct PType(pub u32);
 
 impl Description for PType {
@@ -1,4 +1,4 @@
-use super::{Description, ElfHeader, ElfNAddr, ElfNOff};
+use super::{layout, Description, ElfHeader, ElfNAddr, ElfNOff, FieldSpan};
 use crate::utils::{self, RcSlice};
 
 pub struct SectionHeaderTable {
@@ -34,8 +34,8 @@ impl SectionHeaderTable {
                 ((i + 1) * hdr.e_shentsize) as usize,
             );
             let sh_name = temp.read_u32(0, is_little_endian);
-            let sh_type = SHType(temp.read_u32(4, is_little_endian));
-            let sh_flags = SHFlags32(temp.read_u32(8, is_little_endian));
+            let sh_type = SHType(temp.read_u32(4, is_little_endian), hdr.e_machine.0);
+            let sh_flags = SHFlags32(temp.read_u32(8, is_little_endian), hdr.e_machine.0);
             let sh_addr = temp.read_elfn_addr(12, is_little_endian, is_64_bit);
             let sh_offset = temp.read_elfn_off(16, is_little_endian, is_64_bit);
             let sh_size = temp.read_u32(20, is_little_endian);
@@ -44,6 +44,7 @@ impl SectionHeaderTable {
             let sh_addralign = temp.read_u32(32, is_little_endian);
             let sh_entsize = temp.read_u32(36, is_little_endian);
             shdrs.push(SectionHeader32 {
+                raw: temp.clone(),
                 name: None,
                 sh_name,
                 sh_type,
@@ -74,8 +75,8 @@ impl SectionHeaderTable {
                 ((i + 1) * hdr.e_shentsize) as usize,
             );
             let sh_name = temp.read_u32(0, is_little_endian);
-            let sh_type = SHType(temp.read_u32(4, is_little_endian));
-            let sh_flags = SHFlags64(temp.read_u64(8, is_little_endian));
+            let sh_type = SHType(temp.read_u32(4, is_little_endian), hdr.e_machine.0);
+            let sh_flags = SHFlags64(temp.read_u64(8, is_little_endian), hdr.e_machine.0);
             let sh_addr = temp.read_elfn_addr(16, is_little_endian, is_64_bit);
             let sh_offset = temp.read_elfn_off(24, is_little_endian, is_64_bit);
             let sh_size = temp.read_u64(32, is_little_endian);
@@ -84,6 +85,7 @@ impl SectionHeaderTable {
             let sh_addralign = temp.read_u64(48, is_little_endian);
             let sh_entsize = temp.read_u64(56, is_little_endian);
             shdrs.push(SectionHeader64 {
+                raw: temp.clone(),
                 name: None,
                 sh_name,
                 sh_type,
@@ -103,7 +105,11 @@ impl SectionHeaderTable {
     pub fn populate_names(&mut self, filedata: RcSlice<u8>, hdr: &ElfHeader) {
         match hdr.is_64_bit() {
             true => {
-                let strtab_hdr = &self.shdrs64.as_ref().unwrap()[hdr.e_shstrndx as usize];
+                // files without a section header table (e.g. packed ones) have no names to populate
+                let strtab_hdr = match self.shdrs64.as_ref().unwrap().get(hdr.e_shstrndx as usize) {
+                    Some(strtab_hdr) => strtab_hdr,
+                    None => return,
+                };
                 let offset = strtab_hdr.sh_offset.to_usize();
                 let size = strtab_hdr.sh_size as usize;
                 let strtab;
@@ -122,7 +128,11 @@ impl SectionHeaderTable {
                 }
             }
             false => {
-                let strtab_hdr = &self.shdrs32.as_ref().unwrap()[hdr.e_shstrndx as usize];
+                // files without a section header table (e.g. packed ones) have no names to populate
+                let strtab_hdr = match self.shdrs32.as_ref().unwrap().get(hdr.e_shstrndx as usize) {
+                    Some(strtab_hdr) => strtab_hdr,
+                    None => return,
+                };
                 let offset = strtab_hdr.sh_offset.to_usize();
                 let size = strtab_hdr.sh_size as usize;
                 let strtab;
@@ -143,6 +153,7 @@ impl SectionHeaderTable {
 }
 
 pub struct SectionHeader32 {
+    pub raw: RcSlice<u8>,
     pub name: Option<String>,
     pub sh_name: u32,
     pub sh_type: SHType,
@@ -157,6 +168,7 @@ pub struct SectionHeader32 {
 }
 
 pub struct SectionHeader64 {
+    pub raw: RcSlice<u8>,
     pub name: Option<String>,
     pub sh_name: u32,
     pub sh_type: SHType,
@@ -170,10 +182,109 @@ pub struct SectionHeader64 {
     pub sh_entsize: u64,
 }
 
-pub struct SHType(pub u32);
+impl SectionHeader32 {
+    /// File location of each field of the header
+    pub fn spans(&self) -> Vec<FieldSpan> {
+        layout(
+            self.raw.start,
+            &[
+                ("sh_name", 0, 4),
+                ("sh_type", 4, 4),
+                ("sh_flags", 8, 4),
+                ("sh_addr", 12, 4),
+                ("sh_offset", 16, 4),
+                ("sh_size", 20, 4),
+                ("sh_link", 24, 4),
+                ("sh_info", 28, 4),
+                ("sh_addralign", 32, 4),
+                ("sh_entsize", 36, 4),
+            ],
+        )
+    }
+}
+
+impl SectionHeader64 {
+    /// File location of each field of the header
+    pub fn spans(&self) -> Vec<FieldSpan> {
+        layout(
+            self.raw.start,
+            &[
+                ("sh_name", 0, 4),
+                ("sh_type", 4, 4),
+                ("sh_flags", 8, 8),
+                ("sh_addr", 16, 8),
+                ("sh_offset", 24, 8),
+                ("sh_size", 32, 8),
+                ("sh_link", 40, 4),
+                ("sh_info", 44, 4),
+                ("sh_addralign", 48, 8),
+                ("sh_entsize", 56, 8),
+            ],
+        )
+    }
+}
+
+/// Section type, along with the `e_machine` of the file which determines the meaning of processor specific types
+pub struct SHType(pub u32, pub u16);
+
+impl SHType {
+    fn processor_specific_name(&self) -> Option<&'static str> {
+        let name = match (self.1, self.0) {
+            // ARM
+            (40, 0x70000001) => "ARM_EXIDX",
+            (40, 0x70000002) => "ARM_PREEMPTMAP",
+            (40, 0x70000003) => "ARM_ATTRIBUTES",
+            (40, 0x70000004) => "ARM_DEBUGOVERLAY",
+            (40, 0x70000005) => "ARM_OVERLAYSECTION",
+            // AArch64
+            (183, 0x70000003) => "AARCH64_ATTRIBUTES",
+            (183, 0x70000004) => "AARCH64_AUTH_RELR",
+            (183, 0x70000007) => "AARCH64_MEMTAG_GLOBALS_STATIC",
+            (183, 0x70000008) => "AARCH64_MEMTAG_GLOBALS_DYNAMIC",
+            // MIPS
+            (8, 0x70000000) => "MIPS_LIBLIST",
+            (8, 0x70000001) => "MIPS_MSYM",
+            (8, 0x70000002) => "MIPS_CONFLICT",
+            (8, 0x70000003) => "MIPS_GPTAB",
+            (8, 0x70000004) => "MIPS_UCODE",
+            (8, 0x70000005) => "MIPS_DEBUG",
+            (8, 0x70000006) => "MIPS_REGINFO",
+            (8, 0x7000000d) => "MIPS_OPTIONS",
+            (8, 0x7000001e) => "MIPS_DWARF",
+            (8, 0x7000002a) => "MIPS_ABIFLAGS",
+            (8, 0x7000002b) => "MIPS_XHASH",
+            // x86-64
+            (62, 0x70000001) => "X86_64_UNWIND",
+            // MSP430
+            (105, 0x70000003) => "MSP430_ATTRIBUTES",
+            // C-SKY
+            (252, 0x70000001) => "CSKY_ATTRIBUTES",
+            // RISC-V
+            (243, 0x70000003) => "RISCV_ATTRIBUTES",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// Build attribute sections in the gABI format (e.g. `.ARM.attributes`, `.riscv.attributes`). AArch64 build
+    /// attributes use a different subsection format and are not included
+    pub fn is_attributes(&self) -> bool {
+        matches!(
+            (self.1, self.0),
+            (40, 0x70000003)
+                | (105, 0x70000003)
+                | (252, 0x70000001)
+                | (243, 0x70000003)
+                | (_, 0x6ffffff5)
+        )
+    }
+}
 
 impl Description for SHType {
     fn to_str(&self) -> String {
+        if let Some(name) = self.processor_specific_name() {
+            return name.to_owned();
+        }
         match self.0 {
             0 => "NULL".to_owned(),
             1 => "PROGBITS".to_owned(),
@@ -189,53 +300,144 @@ impl Description for SHType {
             11 => "DYNSYM".to_owned(),
             14 => "INIT_ARRAY".to_owned(),
             15 => "FINI_ARRAY".to_owned(),
+            16 => "PREINIT_ARRAY".to_owned(),
+            17 => "GROUP".to_owned(),
+            18 => "SYMTAB_SHNDX".to_owned(),
+            19 => "RELR".to_owned(),
+            0x60000001 => "ANDROID_REL".to_owned(),
+            0x60000002 => "ANDROID_RELA".to_owned(),
+            0x6fffff00 => "ANDROID_RELR".to_owned(),
+            0x6ffffff5 => "GNU_ATTRIBUTES".to_owned(),
             0x6ffffff6 => "GNU_HASH".to_owned(),
             0x6ffffffd => "VERDEF".to_owned(),
             0x6ffffffe => "VERNEED".to_owned(),
             0x6fffffff => "VERSYM".to_owned(),
+            other @ 0x60000000..=0x6fffffff => format!("<OS specific: 0x{:x}>", other),
+            other @ 0x70000000..=0x7fffffff => format!("<processor specific: 0x{:x}>", other),
+            other @ 0x80000000..=0xffffffff => format!("<application specific: 0x{:x}>", other),
             other => format!("<unknown: 0x{:x}>", other),
         }
     }
 }
 
-pub struct SHFlags32(pub u32);
+// key letter and name of each section flag, following the key printed by readelf
+fn section_flags(machine: u16) -> Vec<(u64, char, &'static str)> {
+    let mut flags = vec![
+        (0x1, 'W', "WRITE"),
+        (0x2, 'A', "ALLOC"),
+        (0x4, 'X', "EXECINSTR"),
+        (0x10, 'M', "MERGE"),
+        (0x20, 'S', "STRINGS"),
+        (0x40, 'I', "INFO_LINK"),
+        (0x80, 'L', "LINK_ORDER"),
+        (0x100, 'O', "OS_NONCONFORMING"),
+        (0x200, 'G', "GROUP"),
+        (0x400, 'T', "TLS"),
+        (0x800, 'C', "COMPRESSED"),
+        (0x200000, 'R', "GNU_RETAIN"),
+        (0x1000000, 'D', "GNU_MBIND"),
+        (0x80000000, 'E', "EXCLUDE"),
+    ];
+    match machine {
+        // EM_MIPS
+        8 => flags.extend([
+            (0x08000000, 'p', "MIPS_NOSTRIP"),
+            (0x10000000, 'p', "MIPS_GPREL"),
+            (0x20000000, 'p', "MIPS_MERGE"),
+            (0x40000000, 'p', "MIPS_ADDR"),
+        ]),
+        // EM_PPC
+        20 => flags.push((0x10000000, 'v', "PPC_VLE")),
+        // EM_ARM
+        40 => flags.push((0x20000000, 'y', "ARM_PURECODE")),
+        // EM_X86_64
+        62 => flags.push((0x10000000, 'l', "X86_64_LARGE")),
+        // EM_AARCH64
+        183 => flags.push((0x20000000, 'y', "AARCH64_PURECODE")),
+        _ => {}
+    }
+    flags
+}
 
-impl Description for SHFlags32 {
-    fn to_str(&self) -> String {
-        let mut s = String::with_capacity(4);
-        if self.0 & 1 != 0 {
-            s.push('W');
-        }
-        if self.0 & 2 != 0 {
-            s.push('A');
+/// Decode section flags into the short letter key and the long form (e.g. `AX` and `ALLOC | EXECINSTR`).
+/// Bits without a known meaning are reported as `x` (unknown), `o` (OS specific) or `p` (processor specific)
+fn describe_section_flags(value: u64, machine: u16) -> (String, String) {
+    let mut key = String::new();
+    let mut names = Vec::new();
+    let mut remaining = value;
+    for (mask, letter, name) in section_flags(machine) {
+        if value & mask != 0 {
+            key.push(letter);
+            names.push(name.to_owned());
+            remaining &= !mask;
         }
-        if self.0 & 4 != 0 {
-            s.push('X');
-        }
-        if self.0 & 0x40 != 0 {
-            s.push('I');
+    }
+    if remaining & !0xfff00000 != 0 {
+        key.push('x');
+        names.push(format!("<unknown: 0x{:x}>", remaining & !0xfff00000));
+    }
+    if remaining & 0x0ff00000 != 0 {
+        key.push('o');
+        names.push(format!("<OS specific: 0x{:x}>", remaining & 0x0ff00000));
+    }
+    if remaining & 0xf0000000 != 0 {
+        key.push('p');
+        names.push(format!(
+            "<processor specific: 0x{:x}>",
+            remaining & 0xf0000000
+        ));
+    }
+    (key, names.join(" | "))
+}
+
+fn section_flags_to_str(value: u64, machine: u16) -> String {
+    match value {
+        0 => String::new(),
+        _ => {
+            let (key, names) = describe_section_flags(value, machine);
+            format!("{} ({})", key, names)
         }
-        s
     }
 }
 
-pub struct SHFlags64(pub u64);
+/// Section flags, along with the `e_machine` of the file which determines the meaning of processor specific bits
+pub struct SHFlags32(pub u32, pub u16);
+
+impl SHFlags32 {
+    /// Short letter key, as printed by readelf
+    pub fn key(&self) -> String {
+        describe_section_flags(self.0 as u64, self.1).0
+    }
+
+    /// Full flag names separated by `|`
+    pub fn names(&self) -> String {
+        describe_section_flags(self.0 as u64, self.1).1
+    }
+}
+
+impl Description for SHFlags32 {
+    fn to_str(&self) -> String {
+        section_flags_to_str(self.0 as u64, self.1)
+    }
+}
+
+/// Section flags, along with the `e_machine` of the file which determines the meaning of processor specific bits
+pub struct SHFlags64(pub u64, pub u16);
+
+impl SHFlags64 {
+    /// Short letter key, as printed by readelf
+    pub fn key(&self) -> String {
+        describe_section_flags(self.0, self.1).0
+    }
+
+    /// Full flag names separated by `|`
+    pub fn names(&self) -> String {
+        describe_section_flags(self.0, self.1).1
+    }
+}
 
 impl Description for SHFlags64 {
     fn to_str(&self) -> String {
-        let mut s = String::with_capacity(4);
-        if self.0 & 1 != 0 {
-            s.push('W');
-        }
-        if self.0 & 2 != 0 {
-            s.push('A');
-        }
-        if self.0 & 4 != 0 {
-            s.push('X');
-        }
-        if self.0 & 0x40 != 0 {
-            s.push('I');
-        }
-        s
+        section_flags_to_str(self.0, self.1)
     }
 }
@@ -0,0 +1,455 @@
+use crate::utils::{self, RcSlice, Table};
+
+/// Build attributes section (`SHT_ARM_ATTRIBUTES`, `SHT_RISCV_ATTRIBUTES`, `SHT_GNU_ATTRIBUTES`...)
+///
+/// The section starts with a format version ('A'), followed by vendor subsections.
+/// Each subsection holds groups of attributes, which apply to the whole file or to specific sections or symbols.
+pub struct AttributesSection {
+    pub format_version: u8,
+    pub subsections: Vec<AttributesSubsection>,
+}
+
+pub struct AttributesSubsection {
+    pub vendor: String,
+    pub groups: Vec<AttributeGroup>,
+}
+
+pub struct AttributeGroup {
+    pub scope: AttributeScope,
+    pub indices: Vec<u64>,
+    pub attributes: Vec<Attribute>,
+}
+
+pub enum AttributeScope {
+    File,
+    Section,
+    Symbol,
+    Unknown(u64),
+}
+
+impl AttributeScope {
+    pub fn to_str(&self) -> String {
+        match self {
+            Self::File => "File".to_owned(),
+            Self::Section => "Section".to_owned(),
+            Self::Symbol => "Symbol".to_owned(),
+            Self::Unknown(tag) => format!("<unknown: 0x{:x}>", tag),
+        }
+    }
+}
+
+pub enum AttributeValue {
+    Int(u64),
+    Str(String),
+    IntStr(u64, String),
+}
+
+pub struct Attribute {
+    pub tag: u64,
+    pub value: AttributeValue,
+    name: String,
+    description: String,
+}
+
+impl Attribute {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value_str(&self) -> &str {
+        &self.description
+    }
+}
+
+impl AttributesSection {
+    pub fn from(data: Option<RcSlice<u8>>, is_little_endian: bool) -> Self {
+        let mut section = Self {
+            format_version: 0,
+            subsections: Vec::new(),
+        };
+        let data = match data {
+            None => return section,
+            Some(data) => data,
+        };
+        let raw = data.get();
+        if raw.is_empty() {
+            return section;
+        }
+        section.format_version = raw[0];
+        // only version 'A' is defined
+        if raw[0] != b'A' {
+            return section;
+        }
+
+        let mut offset = 1;
+        while offset + 4 <= raw.len() {
+            let len = data.read_u32(offset, is_little_endian) as usize;
+            if len < 4 || offset + len > raw.len() {
+                break;
+            }
+            let subsection = &raw[offset + 4..offset + len];
+            let (vendor_len, vendor) = utils::raw_to_str(subsection);
+            let vendor = vendor.unwrap_or("").to_owned();
+            let groups = match vendor_len < subsection.len() {
+                true => Self::parse_groups(
+                    &vendor,
+                    &data,
+                    offset + 4 + vendor_len + 1,
+                    offset + len,
+                    is_little_endian,
+                ),
+                false => Vec::new(),
+            };
+            section
+                .subsections
+                .push(AttributesSubsection { vendor, groups });
+            offset += len;
+        }
+        section
+    }
+
+    fn parse_groups(
+        vendor: &str,
+        data: &RcSlice<u8>,
+        start: usize,
+        end: usize,
+        is_little_endian: bool,
+    ) -> Vec<AttributeGroup> {
+        let raw = data.get();
+        let mut groups = Vec::new();
+        let mut offset = start;
+
+        while offset < end {
+            let group_start = offset;
+            let tag = match utils::read_uleb128(&raw[..end], &mut offset) {
+                None => break,
+                Some(tag) => tag,
+            };
+            if offset + 4 > end {
+                break;
+            }
+            let size = data.read_u32(offset, is_little_endian) as usize;
+            offset += 4;
+            let group_end = group_start + size;
+            if size == 0 || group_end > end {
+                break;
+            }
+
+            let scope = match tag {
+                1 => AttributeScope::File,
+                2 => AttributeScope::Section,
+                3 => AttributeScope::Symbol,
+                other => AttributeScope::Unknown(other),
+            };
+
+            // section and symbol scoped groups start with a 0 terminated list of indices
+            let mut indices = Vec::new();
+            if let AttributeScope::Section | AttributeScope::Symbol = scope {
+                while let Some(index) = utils::read_uleb128(&raw[..group_end], &mut offset) {
+                    if index == 0 {
+                        break;
+                    }
+                    indices.push(index);
+                }
+            }
+
+            let attributes = match scope {
+                AttributeScope::Unknown(_) => Vec::new(),
+                _ => parse_attributes(vendor, &raw[..group_end], offset),
+            };
+            groups.push(AttributeGroup {
+                scope,
+                indices,
+                attributes,
+            });
+            offset = group_end;
+        }
+        groups
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Vendor", "Scope", "Tag", "Value"]);
+        for subsection in &self.subsections {
+            for group in &subsection.groups {
+                let scope = match group.indices.is_empty() {
+                    true => group.scope.to_str(),
+                    false => format!(
+                        "{} ({})",
+                        group.scope.to_str(),
+                        group
+                            .indices
+                            .iter()
+                            .map(|index| index.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                };
+                for attribute in &group.attributes {
+                    table.push(vec![
+                        subsection.vendor.to_owned(),
+                        scope.to_owned(),
+                        attribute.name().to_owned(),
+                        attribute.value_str().to_owned(),
+                    ]);
+                }
+            }
+        }
+        table
+    }
+}
+
+fn parse_attributes(vendor: &str, raw: &[u8], mut offset: usize) -> Vec<Attribute> {
+    let mut attributes = Vec::new();
+    while offset < raw.len() {
+        let tag = match utils::read_uleb128(raw, &mut offset) {
+            None => break,
+            Some(tag) => tag,
+        };
+        let value = match value_kind(vendor, tag) {
+            ValueKind::Int => match utils::read_uleb128(raw, &mut offset) {
+                None => break,
+                Some(value) => AttributeValue::Int(value),
+            },
+            ValueKind::Str => AttributeValue::Str(read_ntbs(raw, &mut offset)),
+            ValueKind::IntStr => match utils::read_uleb128(raw, &mut offset) {
+                None => break,
+                Some(value) => AttributeValue::IntStr(value, read_ntbs(raw, &mut offset)),
+            },
+        };
+        let name = match vendor {
+            "aeabi" => arm_tag_name(tag),
+            "riscv" => riscv_tag_name(tag),
+            _ => None,
+        };
+        let name = match name {
+            Some(name) => name.to_owned(),
+            None => format!("Tag_unknown_{}", tag),
+        };
+        let description = describe(vendor, tag, &value);
+        attributes.push(Attribute {
+            tag,
+            value,
+            name,
+            description,
+        });
+    }
+    attributes
+}
+
+fn read_ntbs(raw: &[u8], offset: &mut usize) -> String {
+    let (len, string) = utils::raw_to_str(&raw[*offset..]);
+    *offset += len + 1;
+    string.unwrap_or("").to_owned()
+}
+
+enum ValueKind {
+    Int,
+    Str,
+    IntStr,
+}
+
+// tags which aren't known follow the generic convention: odd tags >= 32 hold strings, the rest ULEB128
+fn value_kind(vendor: &str, tag: u64) -> ValueKind {
+    match (vendor, tag) {
+        ("aeabi", 4) | ("aeabi", 5) | ("aeabi", 65) | ("aeabi", 67) => ValueKind::Str,
+        ("aeabi", 32) => ValueKind::IntStr,
+        ("riscv", tag) => match tag % 2 {
+            1 => ValueKind::Str,
+            _ => ValueKind::Int,
+        },
+        (_, tag) if tag >= 32 && tag % 2 == 1 => ValueKind::Str,
+        _ => ValueKind::Int,
+    }
+}
+
+fn arm_tag_name(tag: u64) -> Option<&'static str> {
+    let name = match tag {
+        4 => "Tag_CPU_raw_name",
+        5 => "Tag_CPU_name",
+        6 => "Tag_CPU_arch",
+        7 => "Tag_CPU_arch_profile",
+        8 => "Tag_ARM_ISA_use",
+        9 => "Tag_THUMB_ISA_use",
+        10 => "Tag_FP_arch",
+        11 => "Tag_WMMX_arch",
+        12 => "Tag_Advanced_SIMD_arch",
+        13 => "Tag_PCS_config",
+        14 => "Tag_ABI_PCS_R9_use",
+        15 => "Tag_ABI_PCS_RW_data",
+        16 => "Tag_ABI_PCS_RO_data",
+        17 => "Tag_ABI_PCS_GOT_use",
+        18 => "Tag_ABI_PCS_wchar_t",
+        19 => "Tag_ABI_FP_rounding",
+        20 => "Tag_ABI_FP_denormal",
+        21 => "Tag_ABI_FP_exceptions",
+        22 => "Tag_ABI_FP_user_exceptions",
+        23 => "Tag_ABI_FP_number_model",
+        24 => "Tag_ABI_align_needed",
+        25 => "Tag_ABI_align_preserved",
+        26 => "Tag_ABI_enum_size",
+        27 => "Tag_ABI_HardFP_use",
+        28 => "Tag_ABI_VFP_args",
+        29 => "Tag_ABI_WMMX_args",
+        30 => "Tag_ABI_optimization_goals",
+        31 => "Tag_ABI_FP_optimization_goals",
+        32 => "Tag_compatibility",
+        34 => "Tag_CPU_unaligned_access",
+        36 => "Tag_FP_HP_extension",
+        38 => "Tag_ABI_FP_16bit_format",
+        42 => "Tag_MPextension_use",
+        44 => "Tag_DIV_use",
+        46 => "Tag_DSP_extension",
+        48 => "Tag_MVE_arch",
+        50 => "Tag_PAC_extension",
+        52 => "Tag_BTI_extension",
+        64 => "Tag_nodefaults",
+        65 => "Tag_also_compatible_with",
+        66 => "Tag_T2EE_use",
+        67 => "Tag_conformance",
+        68 => "Tag_Virtualization_use",
+        74 => "Tag_BTI_use",
+        76 => "Tag_PACRET_use",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn riscv_tag_name(tag: u64) -> Option<&'static str> {
+    let name = match tag {
+        4 => "Tag_RISCV_stack_align",
+        5 => "Tag_RISCV_arch",
+        6 => "Tag_RISCV_unaligned_access",
+        8 => "Tag_RISCV_priv_spec",
+        10 => "Tag_RISCV_priv_spec_minor",
+        12 => "Tag_RISCV_priv_spec_revision",
+        14 => "Tag_RISCV_atomic_abi",
+        16 => "Tag_RISCV_x3_reg_usage",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn describe(vendor: &str, tag: u64, value: &AttributeValue) -> String {
+    let value = match value {
+        AttributeValue::Str(string) => return string.to_owned(),
+        AttributeValue::IntStr(flag, string) => return format!("{}: {}", flag, string),
+        AttributeValue::Int(value) => *value,
+    };
+    let description = match vendor {
+        "aeabi" => describe_arm(tag, value),
+        "riscv" => describe_riscv(tag, value),
+        _ => None,
+    };
+    match description {
+        Some(description) => description,
+        None => format!("{}", value),
+    }
+}
+
+fn describe_arm(tag: u64, value: u64) -> Option<String> {
+    let description = match (tag, value) {
+        (6, 0) => "Pre-v4",
+        (6, 1) => "v4",
+        (6, 2) => "v4T",
+        (6, 3) => "v5T",
+        (6, 4) => "v5TE",
+        (6, 5) => "v5TEJ",
+        (6, 6) => "v6",
+        (6, 7) => "v6KZ",
+        (6, 8) => "v6T2",
+        (6, 9) => "v6K",
+        (6, 10) => "v7",
+        (6, 11) => "v6-M",
+        (6, 12) => "v6S-M",
+        (6, 13) => "v7E-M",
+        (6, 14) => "v8-A",
+        (6, 15) => "v8-R",
+        (6, 16) => "v8-M.baseline",
+        (6, 17) => "v8-M.mainline",
+        (6, 18) => "v8.1-A",
+        (6, 19) => "v8.2-A",
+        (6, 20) => "v8.3-A",
+        (6, 21) => "v8.1-M.mainline",
+        (6, 22) => "v9-A",
+        (7, 0) => "None",
+        (7, 0x41) => "Application",
+        (7, 0x52) => "Realtime",
+        (7, 0x4d) => "Microcontroller",
+        (7, 0x53) => "Application or Realtime",
+        (8, 0) | (9, 0) | (11, 0) | (12, 0) | (10, 0) => "No",
+        (8, 1) => "Yes",
+        (9, 1) => "Thumb-1",
+        (9, 2) => "Thumb-2",
+        (9, 3) => "Yes",
+        (10, 1) => "VFPv1",
+        (10, 2) => "VFPv2",
+        (10, 3) => "VFPv3",
+        (10, 4) => "VFPv3-D16",
+        (10, 5) => "VFPv4",
+        (10, 6) => "VFPv4-D16",
+        (10, 7) => "FP for ARMv8",
+        (10, 8) => "FPv5/FP-D16 for ARMv8",
+        (11, 1) => "WMMXv1",
+        (11, 2) => "WMMXv2",
+        (12, 1) => "NEONv1",
+        (12, 2) => "NEONv1 with Fused-MAC",
+        (12, 3) => "NEON for ARMv8",
+        (12, 4) => "NEON for ARMv8.1",
+        (14, 0) => "V6",
+        (14, 1) => "SB",
+        (14, 2) => "TLS",
+        (14, 3) => "Unused",
+        (15, 0) | (16, 0) => "Absolute",
+        (15, 1) | (16, 1) => "PC-relative",
+        (15, 2) => "SB-relative",
+        (15, 3) | (16, 2) | (17, 0) | (18, 0) | (23, 0) | (24, 0) => "None",
+        (17, 1) => "Direct",
+        (17, 2) => "GOT-indirect",
+        (18, 2) => "2 bytes",
+        (18, 4) => "4 bytes",
+        (20, 0) | (21, 0) | (22, 0) | (26, 0) => "Unused",
+        (20, 1) | (21, 1) | (22, 1) => "Needed",
+        (20, 2) => "Sign only",
+        (23, 1) => "Finite",
+        (23, 2) => "RTABI",
+        (23, 3) => "IEEE 754",
+        (24, 1) => "8-byte",
+        (24, 2) => "4-byte",
+        (25, 0) => "None",
+        (25, 1) => "8-byte, except leaf SP",
+        (26, 1) => "Small",
+        (26, 2) => "Int",
+        (26, 3) => "Forced to int",
+        (27, 0) => "As Tag_FP_arch",
+        (27, 1) => "SP only",
+        (27, 3) => "DP only",
+        (28, 0) => "AAPCS (base variant, soft float)",
+        (28, 1) => "VFP registers (hard float)",
+        (28, 2) => "Custom",
+        (28, 3) => "Compatible with both",
+        (34, 0) => "None",
+        (34, 1) => "v6",
+        (38, 0) => "None",
+        (38, 1) => "IEEE 754",
+        (38, 2) => "Alternative format",
+        (44, 0) => "Allowed in Thumb-ISA, v7-R or v7-M",
+        (44, 1) => "Not allowed",
+        (44, 2) => "Allowed in v7-A with integer division extension",
+        _ => return None,
+    };
+    Some(description.to_owned())
+}
+
+fn describe_riscv(tag: u64, value: u64) -> Option<String> {
+    let description = match (tag, value) {
+        (4, value) => return Some(format!("{}-bytes", value)),
+        (6, 0) => "No unaligned access",
+        (6, 1) => "Unaligned access",
+        (14, 0) => "Unknown",
+        (14, 1) => "A6C",
+        (14, 2) => "A6S",
+        (14, 3) => "A7",
+        _ => return None,
+    };
+    Some(description.to_owned())
+}
@@ -0,0 +1,407 @@
+use crate::elf::{Description, ElfHeader, Span};
+use crate::utils::{self, RcSlice, Table};
+
+pub struct DynamicSection {
+    pub entries: Vec<DynamicEntry>,
+}
+
+impl DynamicSection {
+    pub fn from(data: Option<RcSlice<u8>>, strtab: Option<RcSlice<u8>>, hdr: &ElfHeader) -> Self {
+        let mut entries = Vec::new();
+        let data = match data {
+            None => return Self { entries },
+            Some(data) => data,
+        };
+        let is_little_endian = hdr.is_little_endian();
+        let is_64_bit = hdr.is_64_bit();
+        let entsize = match is_64_bit {
+            true => 16,
+            false => 8,
+        };
+
+        for i in 0..data.get().len() / entsize {
+            let temp = RcSlice::from(&data, i * entsize, (i + 1) * entsize);
+            let (d_tag, d_val) = match is_64_bit {
+                true => (
+                    temp.read_u64(0, is_little_endian),
+                    temp.read_u64(8, is_little_endian),
+                ),
+                false => (
+                    temp.read_u32(0, is_little_endian) as u64,
+                    temp.read_u32(4, is_little_endian) as u64,
+                ),
+            };
+            let d_tag = DTag(d_tag, hdr.e_machine.0);
+
+            // resolve string values using the linked string table
+            let string = match (d_tag.is_string(), &strtab) {
+                (true, Some(strtab)) if (d_val as usize) < strtab.get().len() => {
+                    match utils::raw_to_str(&strtab.get()[d_val as usize..]).1 {
+                        Ok(string) => Some(string.to_owned()),
+                        Err(_) => None,
+                    }
+                }
+                _ => None,
+            };
+
+            let is_null = d_tag.0 == 0;
+            entries.push(DynamicEntry {
+                span: Span::of(&temp),
+                d_tag,
+                d_val,
+                string,
+            });
+            // everything after DT_NULL is padding
+            if is_null {
+                break;
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn get(&self, tag: u64) -> Option<u64> {
+        self.entries
+            .iter()
+            .find(|entry| entry.d_tag.0 == tag)
+            .map(|entry| entry.d_val)
+    }
+
+    pub fn strings(&self, tag: u64) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.d_tag.0 == tag)
+            .filter_map(|entry| entry.string.as_deref())
+            .collect()
+    }
+
+    pub fn needed(&self) -> Vec<&str> {
+        self.strings(1)
+    }
+
+    pub fn soname(&self) -> Option<&str> {
+        self.strings(14).first().copied()
+    }
+
+    pub fn rpath(&self) -> Option<&str> {
+        self.strings(15).first().copied()
+    }
+
+    pub fn runpath(&self) -> Option<&str> {
+        self.strings(29).first().copied()
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Tag", "Value"]);
+        for entry in &self.entries {
+            table.push(vec![entry.d_tag.to_str(), entry.value_str()]);
+        }
+        table
+    }
+}
+
+pub struct DynamicEntry {
+    /// Location of the dynamic entry
+    pub span: Span,
+    pub d_tag: DTag,
+    pub d_val: u64,
+    pub string: Option<String>,
+}
+
+impl DynamicEntry {
+    pub fn value_str(&self) -> String {
+        if let Some(string) = &self.string {
+            return match self.d_tag.0 {
+                1 => format!("Shared library: [{}]", string),
+                14 => format!("Library soname: [{}]", string),
+                15 => format!("Library rpath: [{}]", string),
+                29 => format!("Library runpath: [{}]", string),
+                _ => string.to_owned(),
+            };
+        }
+        match self.d_tag.0 {
+            // DT_FLAGS
+            30 => DynFlags(self.d_val).to_str(),
+            // DT_FLAGS_1
+            0x6ffffffb => DynFlags1(self.d_val).to_str(),
+            // DT_PLTREL
+            20 => match self.d_val {
+                7 => "RELA".to_owned(),
+                17 => "REL".to_owned(),
+                other => format!("{}", other),
+            },
+            // sizes
+            2 | 8 | 9 | 10 | 11 | 18 | 19 | 27 | 28 | 33 | 35 | 37 | 0x60000010 | 0x60000012
+            | 0x6fffe001 | 0x6fffe003 | 0x6ffffdf6 | 0x6ffffdf7 | 0x6ffffdf9 | 0x6ffffdfa
+            | 0x6ffffdfb | 0x6ffffdfe | 0x6ffffdff => {
+                format!("{} (bytes)", self.d_val)
+            }
+            // counts
+            0x6fffe005 | 0x6ffffff9 | 0x6ffffffa | 0x6ffffffd | 0x6fffffff => {
+                format!("{}", self.d_val)
+            }
+            _ if self.d_tag.is_mips_count() => format!("{}", self.d_val),
+            _ => format!("0x{:x}", self.d_val),
+        }
+    }
+}
+
+/// Dynamic entry tag, along with the `e_machine` of the file which determines the meaning of processor specific tags
+pub struct DTag(pub u64, pub u16);
+
+impl DTag {
+    pub fn is_string(&self) -> bool {
+        // NEEDED, SONAME, RPATH, RUNPATH, CONFIG, DEPAUDIT, AUDIT, AUXILIARY, FILTER
+        matches!(
+            self.0,
+            1 | 14 | 15 | 29 | 0x6ffffefa | 0x6ffffefb | 0x6ffffefc | 0x7ffffffd | 0x7fffffff
+        )
+    }
+
+    pub fn is_address(&self) -> bool {
+        // PLTGOT, HASH, STRTAB, SYMTAB, RELA, INIT, FINI, REL, JMPREL, INIT_ARRAY, FINI_ARRAY, PREINIT_ARRAY, RELR,
+        // GNU_HASH, VERSYM, VERDEF, VERNEED
+        matches!(
+            self.0,
+            3 | 4
+                | 5
+                | 6
+                | 7
+                | 12
+                | 13
+                | 17
+                | 23
+                | 25
+                | 26
+                | 32
+                | 36
+                | 0x6ffffef5
+                | 0x6ffffff0
+                | 0x6ffffffc
+                | 0x6ffffffe
+        )
+    }
+
+    fn is_mips_count(&self) -> bool {
+        // LOCAL_GOTNO, CONFLICTNO, LIBLISTNO, SYMTABNO, UNREFEXTNO, GOTSYM, HIPAGENO
+        self.1 == 8
+            && matches!(
+                self.0,
+                0x7000000a
+                    | 0x7000000b
+                    | 0x70000010
+                    | 0x70000011
+                    | 0x70000012
+                    | 0x70000013
+                    | 0x70000014
+            )
+    }
+
+    fn processor_specific_name(&self) -> Option<&'static str> {
+        let name = match (self.1, self.0) {
+            // MIPS
+            (8, 0x70000001) => "MIPS_RLD_VERSION",
+            (8, 0x70000002) => "MIPS_TIME_STAMP",
+            (8, 0x70000003) => "MIPS_ICHECKSUM",
+            (8, 0x70000004) => "MIPS_IVERSION",
+            (8, 0x70000005) => "MIPS_FLAGS",
+            (8, 0x70000006) => "MIPS_BASE_ADDRESS",
+            (8, 0x70000007) => "MIPS_MSYM",
+            (8, 0x70000008) => "MIPS_CONFLICT",
+            (8, 0x70000009) => "MIPS_LIBLIST",
+            (8, 0x7000000a) => "MIPS_LOCAL_GOTNO",
+            (8, 0x7000000b) => "MIPS_CONFLICTNO",
+            (8, 0x70000010) => "MIPS_LIBLISTNO",
+            (8, 0x70000011) => "MIPS_SYMTABNO",
+            (8, 0x70000012) => "MIPS_UNREFEXTNO",
+            (8, 0x70000013) => "MIPS_GOTSYM",
+            (8, 0x70000014) => "MIPS_HIPAGENO",
+            (8, 0x70000016) => "MIPS_RLD_MAP",
+            (8, 0x70000017) => "MIPS_DELTA_CLASS",
+            (8, 0x70000018) => "MIPS_DELTA_CLASS_NO",
+            (8, 0x70000019) => "MIPS_DELTA_INSTANCE",
+            (8, 0x7000001a) => "MIPS_DELTA_INSTANCE_NO",
+            (8, 0x7000001b) => "MIPS_DELTA_RELOC",
+            (8, 0x7000001c) => "MIPS_DELTA_RELOC_NO",
+            (8, 0x7000001d) => "MIPS_DELTA_SYM",
+            (8, 0x7000001e) => "MIPS_DELTA_SYM_NO",
+            (8, 0x70000020) => "MIPS_DELTA_CLASSSYM",
+            (8, 0x70000021) => "MIPS_DELTA_CLASSSYM_NO",
+            (8, 0x70000022) => "MIPS_CXX_FLAGS",
+            (8, 0x70000023) => "MIPS_PIXIE_INIT",
+            (8, 0x70000024) => "MIPS_SYMBOL_LIB",
+            (8, 0x70000025) => "MIPS_LOCALPAGE_GOTIDX",
+            (8, 0x70000026) => "MIPS_LOCAL_GOTIDX",
+            (8, 0x70000027) => "MIPS_HIDDEN_GOTIDX",
+            (8, 0x70000028) => "MIPS_PROTECTED_GOTIDX",
+            (8, 0x70000029) => "MIPS_OPTIONS",
+            (8, 0x7000002a) => "MIPS_INTERFACE",
+            (8, 0x7000002b) => "MIPS_DYNSTR_ALIGN",
+            (8, 0x7000002c) => "MIPS_INTERFACE_SIZE",
+            (8, 0x7000002d) => "MIPS_RLD_TEXT_RESOLVE_ADDR",
+            (8, 0x7000002e) => "MIPS_PERF_SUFFIX",
+            (8, 0x7000002f) => "MIPS_COMPACT_SIZE",
+            (8, 0x70000030) => "MIPS_GP_VALUE",
+            (8, 0x70000031) => "MIPS_AUX_DYNAMIC",
+            (8, 0x70000032) => "MIPS_PLTGOT",
+            (8, 0x70000034) => "MIPS_RWPLT",
+            (8, 0x70000035) => "MIPS_RLD_MAP_REL",
+            (8, 0x70000036) => "MIPS_XHASH",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+impl Description for DTag {
+    fn to_str(&self) -> String {
+        if let Some(name) = self.processor_specific_name() {
+            return name.to_owned();
+        }
+        match self.0 {
+            0 => "NULL".to_owned(),
+            1 => "NEEDED".to_owned(),
+            2 => "PLTRELSZ".to_owned(),
+            3 => "PLTGOT".to_owned(),
+            4 => "HASH".to_owned(),
+            5 => "STRTAB".to_owned(),
+            6 => "SYMTAB".to_owned(),
+            7 => "RELA".to_owned(),
+            8 => "RELASZ".to_owned(),
+            9 => "RELAENT".to_owned(),
+            10 => "STRSZ".to_owned(),
+            11 => "SYMENT".to_owned(),
+            12 => "INIT".to_owned(),
+            13 => "FINI".to_owned(),
+            14 => "SONAME".to_owned(),
+            15 => "RPATH".to_owned(),
+            16 => "SYMBOLIC".to_owned(),
+            17 => "REL".to_owned(),
+            18 => "RELSZ".to_owned(),
+            19 => "RELENT".to_owned(),
+            20 => "PLTREL".to_owned(),
+            21 => "DEBUG".to_owned(),
+            22 => "TEXTREL".to_owned(),
+            23 => "JMPREL".to_owned(),
+            24 => "BIND_NOW".to_owned(),
+            25 => "INIT_ARRAY".to_owned(),
+            26 => "FINI_ARRAY".to_owned(),
+            27 => "INIT_ARRAYSZ".to_owned(),
+            28 => "FINI_ARRAYSZ".to_owned(),
+            29 => "RUNPATH".to_owned(),
+            30 => "FLAGS".to_owned(),
+            32 => "PREINIT_ARRAY".to_owned(),
+            33 => "PREINIT_ARRAYSZ".to_owned(),
+            34 => "SYMTAB_SHNDX".to_owned(),
+            35 => "RELRSZ".to_owned(),
+            36 => "RELR".to_owned(),
+            37 => "RELRENT".to_owned(),
+            0x6000000f => "ANDROID_REL".to_owned(),
+            0x60000010 => "ANDROID_RELSZ".to_owned(),
+            0x60000011 => "ANDROID_RELA".to_owned(),
+            0x60000012 => "ANDROID_RELASZ".to_owned(),
+            0x6fffe000 => "ANDROID_RELR".to_owned(),
+            0x6fffe001 => "ANDROID_RELRSZ".to_owned(),
+            0x6fffe003 => "ANDROID_RELRENT".to_owned(),
+            0x6fffe005 => "ANDROID_RELRCOUNT".to_owned(),
+            0x6ffffdf5 => "GNU_PRELINKED".to_owned(),
+            0x6ffffdf6 => "GNU_CONFLICTSZ".to_owned(),
+            0x6ffffdf7 => "GNU_LIBLISTSZ".to_owned(),
+            0x6ffffdf8 => "CHECKSUM".to_owned(),
+            0x6ffffdf9 => "PLTPADSZ".to_owned(),
+            0x6ffffdfa => "MOVEENT".to_owned(),
+            0x6ffffdfb => "MOVESZ".to_owned(),
+            0x6ffffdfc => "FEATURE_1".to_owned(),
+            0x6ffffdfd => "POSFLAG_1".to_owned(),
+            0x6ffffdfe => "SYMINSZ".to_owned(),
+            0x6ffffdff => "SYMINENT".to_owned(),
+            0x6ffffef5 => "GNU_HASH".to_owned(),
+            0x6ffffef6 => "TLSDESC_PLT".to_owned(),
+            0x6ffffef7 => "TLSDESC_GOT".to_owned(),
+            0x6ffffef8 => "GNU_CONFLICT".to_owned(),
+            0x6ffffef9 => "GNU_LIBLIST".to_owned(),
+            0x6ffffefa => "CONFIG".to_owned(),
+            0x6ffffefb => "DEPAUDIT".to_owned(),
+            0x6ffffefc => "AUDIT".to_owned(),
+            0x6ffffefd => "PLTPAD".to_owned(),
+            0x6ffffefe => "MOVETAB".to_owned(),
+            0x6ffffeff => "SYMINFO".to_owned(),
+            0x6ffffff0 => "VERSYM".to_owned(),
+            0x6ffffff9 => "RELACOUNT".to_owned(),
+            0x6ffffffa => "RELCOUNT".to_owned(),
+            0x6ffffffb => "FLAGS_1".to_owned(),
+            0x6ffffffc => "VERDEF".to_owned(),
+            0x6ffffffd => "VERDEFNUM".to_owned(),
+            0x6ffffffe => "VERNEED".to_owned(),
+            0x6fffffff => "VERNEEDNUM".to_owned(),
+            0x7ffffffd => "AUXILIARY".to_owned(),
+            0x7fffffff => "FILTER".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct DynFlags(pub u64);
+
+impl Description for DynFlags {
+    fn to_str(&self) -> String {
+        let names = [
+            (0x1, "ORIGIN"),
+            (0x2, "SYMBOLIC"),
+            (0x4, "TEXTREL"),
+            (0x8, "BIND_NOW"),
+            (0x10, "STATIC_TLS"),
+        ];
+        flags_to_str(self.0, &names)
+    }
+}
+
+pub struct DynFlags1(pub u64);
+
+impl Description for DynFlags1 {
+    fn to_str(&self) -> String {
+        let names = [
+            (0x1, "NOW"),
+            (0x2, "GLOBAL"),
+            (0x4, "GROUP"),
+            (0x8, "NODELETE"),
+            (0x10, "LOADFLTR"),
+            (0x20, "INITFIRST"),
+            (0x40, "NOOPEN"),
+            (0x80, "ORIGIN"),
+            (0x100, "DIRECT"),
+            (0x200, "TRANS"),
+            (0x400, "INTERPOSE"),
+            (0x800, "NODEFLIB"),
+            (0x1000, "NODUMP"),
+            (0x2000, "CONFALT"),
+            (0x4000, "ENDFILTEE"),
+            (0x8000, "DISPRELDNE"),
+            (0x10000, "DISPRELPND"),
+            (0x20000, "NODIRECT"),
+            (0x40000, "IGNMULDEF"),
+            (0x80000, "NOKSYMS"),
+            (0x100000, "NOHDR"),
+            (0x200000, "EDITED"),
+            (0x400000, "NORELOC"),
+            (0x800000, "SYMINTPOSE"),
+            (0x1000000, "GLOBAUDIT"),
+            (0x2000000, "SINGLETON"),
+            (0x4000000, "STUB"),
+            (0x8000000, "PIE"),
+        ];
+        flags_to_str(self.0, &names)
+    }
+}
+
+fn flags_to_str(value: u64, names: &[(u64, &str)]) -> String {
+    let mut flags: Vec<String> = names
+        .iter()
+        .filter(|(bit, _)| value & bit != 0)
+        .map(|(_, name)| name.to_string())
+        .collect();
+    let known = names.iter().fold(0, |known, (bit, _)| known | bit);
+    if value & !known != 0 {
+        flags.push(format!("<unknown: 0x{:x}>", value & !known));
+    }
+    flags.join(" ")
+}
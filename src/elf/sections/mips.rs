@@ -0,0 +1,332 @@
+use crate::elf::Description;
+use crate::utils::{RcSlice, Table};
+
+/// Contents of a `.MIPS.abiflags` section (`Elf_MIPS_ABIFlags_v0`)
+pub struct MipsAbiFlags {
+    pub version: u16,
+    pub isa_level: u8,
+    pub isa_rev: u8,
+    pub gpr_size: MipsRegSize,
+    pub cpr1_size: MipsRegSize,
+    pub cpr2_size: MipsRegSize,
+    pub fp_abi: MipsFpAbi,
+    pub isa_ext: MipsIsaExt,
+    pub ases: MipsAses,
+    pub flags1: u32,
+    pub flags2: u32,
+}
+
+impl MipsAbiFlags {
+    pub fn from(data: Option<RcSlice<u8>>, is_little_endian: bool) -> Option<Self> {
+        let data = data?;
+        if data.get().len() < 24 {
+            return None;
+        }
+        let raw = data.get();
+        Some(Self {
+            version: data.read_u16(0, is_little_endian),
+            isa_level: raw[2],
+            isa_rev: raw[3],
+            gpr_size: MipsRegSize(raw[4]),
+            cpr1_size: MipsRegSize(raw[5]),
+            cpr2_size: MipsRegSize(raw[6]),
+            fp_abi: MipsFpAbi(raw[7]),
+            isa_ext: MipsIsaExt(data.read_u32(8, is_little_endian)),
+            ases: MipsAses(data.read_u32(12, is_little_endian)),
+            flags1: data.read_u32(16, is_little_endian),
+            flags2: data.read_u32(20, is_little_endian),
+        })
+    }
+
+    pub fn isa(&self) -> String {
+        match (self.isa_level, self.isa_rev) {
+            (level @ (32 | 64), rev) if rev > 1 => format!("MIPS{}r{}", level, rev),
+            (level, _) => format!("MIPS{}", level),
+        }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Field", "Value"]);
+        let mut push = |field: &str, value: String| table.push(vec![field.to_owned(), value]);
+        push("Version", format!("{}", self.version));
+        push("ISA", self.isa());
+        push("GPR size", self.gpr_size.to_str());
+        push("CPR1 size", self.cpr1_size.to_str());
+        push("CPR2 size", self.cpr2_size.to_str());
+        push("FP ABI", self.fp_abi.to_str());
+        push("ISA extension", self.isa_ext.to_str());
+        push("ASEs", self.ases.to_str());
+        push(
+            "FLAGS 1",
+            match self.flags1 & 1 {
+                0 => format!("0x{:08x}", self.flags1),
+                _ => format!("0x{:08x} (ODDSPREG)", self.flags1),
+            },
+        );
+        push("FLAGS 2", format!("0x{:08x}", self.flags2));
+        table
+    }
+}
+
+pub struct MipsRegSize(pub u8);
+
+impl Description for MipsRegSize {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "0".to_owned(),
+            1 => "32".to_owned(),
+            2 => "64".to_owned(),
+            3 => "128".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct MipsFpAbi(pub u8);
+
+impl Description for MipsFpAbi {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "Hard or soft float".to_owned(),
+            1 => "Hard float (double precision)".to_owned(),
+            2 => "Hard float (single precision)".to_owned(),
+            3 => "Soft float".to_owned(),
+            4 => "Hard float (MIPS32r2 64-bit FPU 12 callee-saved)".to_owned(),
+            5 => "Hard float (32-bit CPU, Any FPU)".to_owned(),
+            6 => "Hard float (32-bit CPU, 64-bit FPU)".to_owned(),
+            7 => "Hard float compat (32-bit CPU, 64-bit FPU)".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct MipsIsaExt(pub u32);
+
+impl Description for MipsIsaExt {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "None".to_owned(),
+            1 => "RMI XLR".to_owned(),
+            2 => "Cavium Networks Octeon2".to_owned(),
+            3 => "Cavium Networks OcteonP".to_owned(),
+            4 => "Loongson 3A".to_owned(),
+            5 => "Cavium Networks Octeon".to_owned(),
+            6 => "Toshiba R5900".to_owned(),
+            7 => "MIPS R4650".to_owned(),
+            8 => "LSI R4010".to_owned(),
+            9 => "NEC VR4100".to_owned(),
+            10 => "Toshiba R3900".to_owned(),
+            11 => "MIPS R10000".to_owned(),
+            12 => "Broadcom SB-1".to_owned(),
+            13 => "NEC VR4111/VR4181".to_owned(),
+            14 => "NEC VR4120".to_owned(),
+            15 => "NEC VR5400".to_owned(),
+            16 => "NEC VR5500".to_owned(),
+            17 => "ST Microelectronics Loongson 2E".to_owned(),
+            18 => "ST Microelectronics Loongson 2F".to_owned(),
+            19 => "Cavium Networks Octeon3".to_owned(),
+            20 => "Imagination interAptiv MR2".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct MipsAses(pub u32);
+
+impl Description for MipsAses {
+    fn to_str(&self) -> String {
+        let names = [
+            (0x1, "DSP"),
+            (0x2, "DSPR2"),
+            (0x4, "Enhanced VA Scheme"),
+            (0x8, "MCU"),
+            (0x10, "MDMX"),
+            (0x20, "MIPS-3D"),
+            (0x40, "MT"),
+            (0x80, "SmartMIPS"),
+            (0x100, "VZ"),
+            (0x200, "MSA"),
+            (0x400, "MIPS16"),
+            (0x800, "microMIPS"),
+            (0x1000, "XPA"),
+            (0x2000, "DSPR3"),
+            (0x4000, "MIPS16e2"),
+            (0x8000, "CRC"),
+            (0x20000, "GINV"),
+            (0x40000, "Loongson MMI"),
+            (0x80000, "Loongson CAM"),
+            (0x100000, "Loongson EXT"),
+            (0x200000, "Loongson EXT2"),
+        ];
+        if self.0 == 0 {
+            return "None".to_owned();
+        }
+        let mut ases: Vec<String> = names
+            .iter()
+            .filter(|(bit, _)| self.0 & bit != 0)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        let known = names.iter().fold(0, |known, (bit, _)| known | bit);
+        if self.0 & !known != 0 {
+            ases.push(format!("<unknown: 0x{:x}>", self.0 & !known));
+        }
+        ases.join(", ")
+    }
+}
+
+/// Register usage information, found in `.reginfo` sections (32 bit)
+/// and in `ODK_REGINFO` options of `.MIPS.options` sections
+pub struct MipsRegInfo {
+    pub gprmask: u32,
+    pub cprmask: [u32; 4],
+    pub gp_value: u64,
+}
+
+impl MipsRegInfo {
+    /// `Elf32_RegInfo` is 24 bytes long, `Elf64_RegInfo` is 32 bytes long with padding after the GPR mask and a 64 bit GP value
+    pub fn from(data: &RcSlice<u8>, is_64_bit: bool, is_little_endian: bool) -> Option<Self> {
+        let (size, cprmask_offset) = match is_64_bit {
+            true => (32, 8),
+            false => (24, 4),
+        };
+        if data.get().len() < size {
+            return None;
+        }
+        let mut cprmask = [0; 4];
+        for (i, mask) in cprmask.iter_mut().enumerate() {
+            *mask = data.read_u32(cprmask_offset + i * 4, is_little_endian);
+        }
+        let gp_value = match is_64_bit {
+            true => data.read_u64(24, is_little_endian),
+            false => data.read_u32(20, is_little_endian) as u64,
+        };
+        Some(Self {
+            gprmask: data.read_u32(0, is_little_endian),
+            cprmask,
+            gp_value,
+        })
+    }
+
+    fn push_rows(&self, table: &mut Table, prefix: &str) {
+        table.push(vec![
+            format!("{}GPR mask", prefix),
+            format!("0x{:08x}", self.gprmask),
+        ]);
+        table.push(vec![
+            format!("{}CPR masks", prefix),
+            self.cprmask
+                .iter()
+                .map(|mask| format!("0x{:08x}", mask))
+                .collect::<Vec<String>>()
+                .join(" "),
+        ]);
+        table.push(vec![
+            format!("{}GP value", prefix),
+            format!("0x{:x}", self.gp_value),
+        ]);
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Field", "Value"]);
+        self.push_rows(&mut table, "");
+        table
+    }
+}
+
+/// A `.MIPS.options` section, made of variable sized `Elf_Options` descriptors
+pub struct MipsOptions {
+    pub options: Vec<MipsOption>,
+}
+
+pub struct MipsOption {
+    pub kind: MipsOptionKind,
+    pub size: u8,
+    pub section: u16,
+    pub info: u32,
+    pub reginfo: Option<MipsRegInfo>,
+}
+
+impl MipsOptions {
+    pub fn from(data: Option<RcSlice<u8>>, is_64_bit: bool, is_little_endian: bool) -> Self {
+        let mut options = Vec::new();
+        let data = match data {
+            None => return Self { options },
+            Some(data) => data,
+        };
+
+        let mut offset = 0;
+        while offset + 8 <= data.get().len() {
+            let raw = data.get();
+            let kind = MipsOptionKind(raw[offset]);
+            let size = raw[offset + 1];
+            // the size includes the descriptor itself
+            let end = offset + size as usize;
+            if size < 8 || end > raw.len() {
+                break;
+            }
+            let reginfo = match kind.0 {
+                // ODK_REGINFO
+                1 => MipsRegInfo::from(
+                    &RcSlice::from(&data, offset + 8, end),
+                    is_64_bit,
+                    is_little_endian,
+                ),
+                _ => None,
+            };
+            options.push(MipsOption {
+                kind,
+                size,
+                section: data.read_u16(offset + 2, is_little_endian),
+                info: data.read_u32(offset + 4, is_little_endian),
+                reginfo,
+            });
+            offset = end;
+        }
+        Self { options }
+    }
+
+    pub fn reginfo(&self) -> Option<&MipsRegInfo> {
+        self.options
+            .iter()
+            .find_map(|option| option.reginfo.as_ref())
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Field", "Value"]);
+        for option in &self.options {
+            table.push(vec![
+                option.kind.to_str(),
+                format!(
+                    "size: {}, section: {}, info: 0x{:x}",
+                    option.size, option.section, option.info
+                ),
+            ]);
+            if let Some(reginfo) = &option.reginfo {
+                reginfo.push_rows(&mut table, "    ");
+            }
+        }
+        table
+    }
+}
+
+pub struct MipsOptionKind(pub u8);
+
+impl Description for MipsOptionKind {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "NULL".to_owned(),
+            1 => "REGINFO".to_owned(),
+            2 => "EXCEPTIONS".to_owned(),
+            3 => "PAD".to_owned(),
+            4 => "HWPATCH".to_owned(),
+            5 => "FILL".to_owned(),
+            6 => "TAGS".to_owned(),
+            7 => "HWAND".to_owned(),
+            8 => "HWOR".to_owned(),
+            9 => "GP_GROUP".to_owned(),
+            10 => "IDENT".to_owned(),
+            11 => "PAGESIZE".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
@@ -1,5 +1,19 @@
+mod attributes;
+pub use attributes::*;
+mod dynamic;
+pub use dynamic::*;
+mod mips;
+pub use mips::*;
+mod notes;
+pub use notes::*;
+mod reloc;
+pub use reloc::*;
 mod strtab;
 pub use strtab::*;
+mod symtab;
+pub use symtab::*;
+mod versions;
+pub use versions::*;
 
 use super::{Description, ElfHeader, SHType, SectionHeaderTable};
 use crate::utils::RcSlice;
@@ -7,6 +21,17 @@ use crate::utils::RcSlice;
 pub enum SectionType {
     Generic,
     Strtab(StrtabSection),
+    Symtab(SymtabSection),
+    Attributes(AttributesSection),
+    Dynamic(DynamicSection),
+    MipsAbiFlags(MipsAbiFlags),
+    MipsRegInfo(MipsRegInfo),
+    MipsOptions(MipsOptions),
+    Relocation(RelocationSection),
+    Note(NoteSection),
+    Versym(VersymSection),
+    Verneed(VerneedSection),
+    Verdef(VerdefSection),
 }
 
 pub struct Sections(pub Vec<Section>);
@@ -37,14 +62,14 @@ impl Sections {
                             },
                         },
                     };
-                    sections.push(Section::from(
-                        index,
-                        name,
-                        file_offset,
-                        size,
-                        &sh_type,
-                        data,
-                    ));
+                    let mut section = Section::from(index, name, file_offset, size, &sh_type, data);
+                    section.addr = shdr.sh_addr.to_u64();
+                    section.flags = shdr.sh_flags.0;
+                    section.link = shdr.sh_link;
+                    section.info = shdr.sh_info;
+                    section.addralign = shdr.sh_addralign;
+                    section.entsize = shdr.sh_entsize;
+                    sections.push(section);
                 }
             }
             false => {
@@ -68,19 +93,66 @@ impl Sections {
                             },
                         },
                     };
-                    sections.push(Section::from(
-                        index,
-                        name,
-                        file_offset,
-                        size,
-                        &sh_type,
-                        data,
-                    ));
+                    let mut section = Section::from(index, name, file_offset, size, &sh_type, data);
+                    section.addr = shdr.sh_addr.to_u64();
+                    section.flags = shdr.sh_flags.0 as u64;
+                    section.link = shdr.sh_link;
+                    section.info = shdr.sh_info;
+                    section.addralign = shdr.sh_addralign as u64;
+                    section.entsize = shdr.sh_entsize as u64;
+                    sections.push(section);
                 }
             }
         }
+
+        // sections which reference other sections (e.g. a symbol table and its string table)
+        // or depend on the ELF header can only be decoded once all sections are known
+        for i in 0..sections.len() {
+            if let Some(section_type) = Section::decode(&sections[i], &sections, hdr) {
+                sections[i].section_type = section_type;
+            }
+        }
+        // relocations reference the symbols of an already decoded symbol table
+        for i in 0..sections.len() {
+            if let Some(section_type) = Section::decode_relocations(&sections[i], &sections, hdr) {
+                sections[i].section_type = section_type;
+            }
+        }
+
         Sections(sections)
     }
+
+    pub fn by_name(&self, name: &str) -> Option<&Section> {
+        self.0
+            .iter()
+            .find(|section| section.name.as_deref() == Some(name))
+    }
+
+    /// Find the section which contains a virtual address
+    pub fn by_addr(&self, addr: u64) -> Option<&Section> {
+        self.0.iter().find(|section| {
+            section.addr != 0 && addr >= section.addr && addr - section.addr < section.size as u64
+        })
+    }
+
+    pub fn dynamic(&self) -> Option<&DynamicSection> {
+        self.0
+            .iter()
+            .find_map(|section| match &section.section_type {
+                SectionType::Dynamic(dynamic) => Some(dynamic),
+                _ => None,
+            })
+    }
+
+    /// Iterate over all symbols of all symbol tables (SYMTAB and DYNSYM)
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.0
+            .iter()
+            .flat_map(|section| match &section.section_type {
+                SectionType::Symtab(symtab) => symtab.symbols.iter(),
+                _ => [].iter(),
+            })
+    }
 }
 
 pub struct Section {
@@ -88,6 +160,12 @@ pub struct Section {
     pub name: Option<String>,
     pub file_offset: usize,
     pub size: usize,
+    pub addr: u64,
+    pub flags: u64,
+    pub link: u32,
+    pub info: u32,
+    pub addralign: u64,
+    pub entsize: u64,
     sh_type: SHType,
     pub section_type: SectionType,
     data: Option<RcSlice<u8>>,
@@ -115,20 +193,166 @@ impl Section {
             name,
             file_offset,
             size,
-            sh_type: SHType(sh_type.0),
+            addr: 0,
+            flags: 0,
+            link: 0,
+            info: 0,
+            addralign: 0,
+            entsize: 0,
+            sh_type: SHType(sh_type.0, sh_type.1),
             section_type,
             data,
         }
     }
 
+    fn decode(section: &Section, sections: &[Section], hdr: &ElfHeader) -> Option<SectionType> {
+        let linked = sections.get(section.link as usize);
+        match section.sh_type.0 {
+            // SYMTAB, DYNSYM
+            2 | 11 => Some(SectionType::Symtab(SymtabSection::from(
+                section.data.clone(),
+                linked.and_then(|linked| linked.data.clone()),
+                section.entsize as usize,
+                hdr,
+            ))),
+            // DYNAMIC
+            6 => Some(SectionType::Dynamic(DynamicSection::from(
+                section.data.clone(),
+                linked.and_then(|linked| linked.data.clone()),
+                hdr,
+            ))),
+            // NOTE
+            7 => Some(SectionType::Note(NoteSection::from(
+                section.data.clone(),
+                section.addralign,
+                hdr,
+            ))),
+            // GNU_verdef
+            0x6ffffffd => Some(SectionType::Verdef(VerdefSection::from(
+                section.data.clone(),
+                linked.and_then(|linked| linked.data.clone()),
+                section.info,
+                hdr,
+            ))),
+            // GNU_verneed
+            0x6ffffffe => Some(SectionType::Verneed(VerneedSection::from(
+                section.data.clone(),
+                linked.and_then(|linked| linked.data.clone()),
+                section.info,
+                hdr,
+            ))),
+            // GNU_versym
+            0x6fffffff => Some(SectionType::Versym(VersymSection::from(
+                section.data.clone(),
+                hdr,
+            ))),
+            _ if section.sh_type.is_attributes() => Some(SectionType::Attributes(
+                AttributesSection::from(section.data.clone(), hdr.is_little_endian()),
+            )),
+            // MIPS_ABIFLAGS
+            0x7000002a if hdr.e_machine.0 == 8 => Some(SectionType::MipsAbiFlags(
+                MipsAbiFlags::from(section.data.clone(), hdr.is_little_endian())?,
+            )),
+            // MIPS_REGINFO
+            0x70000006 if hdr.e_machine.0 == 8 => Some(SectionType::MipsRegInfo(
+                MipsRegInfo::from(section.data.as_ref()?, false, hdr.is_little_endian())?,
+            )),
+            // MIPS_OPTIONS
+            0x7000000d if hdr.e_machine.0 == 8 => {
+                Some(SectionType::MipsOptions(MipsOptions::from(
+                    section.data.clone(),
+                    hdr.is_64_bit(),
+                    hdr.is_little_endian(),
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    fn decode_relocations(
+        section: &Section,
+        sections: &[Section],
+        hdr: &ElfHeader,
+    ) -> Option<SectionType> {
+        let symbols = match sections.get(section.link as usize).map(|s| &s.section_type) {
+            Some(SectionType::Symtab(symtab)) => Some(&symtab.symbols[..]),
+            _ => None,
+        };
+        let format = match section.sh_type.0 {
+            // RELA
+            4 => RelocationFormat::Rela,
+            // REL
+            9 => RelocationFormat::Rel,
+            // ANDROID_REL, ANDROID_RELA
+            0x60000001 | 0x60000002 => {
+                let format = match section.sh_type.0 {
+                    0x60000001 => RelocationFormat::Rel,
+                    _ => RelocationFormat::Rela,
+                };
+                return RelocationSection::from_android(section.data.clone(), symbols, format, hdr)
+                    .map(SectionType::Relocation);
+            }
+            // RELR, ANDROID_RELR
+            19 | 0x6fffff00 => {
+                return Some(SectionType::Relocation(RelocationSection::from_relr(
+                    section.data.clone(),
+                    hdr,
+                )))
+            }
+            _ => return None,
+        };
+        let mut relocations = RelocationSection::from(section.data.clone(), symbols, format, hdr);
+
+        // section symbols have no name of their own, so refer to them by the section name
+        for reloc in relocations.relocations.iter_mut() {
+            let symbol = symbols.and_then(|symbols| symbols.get(reloc.sym as usize));
+            if let (None, Some(symbol)) = (&reloc.symbol_name, symbol) {
+                if symbol.sym_type().0 == 3 {
+                    reloc.symbol_name = sections
+                        .get(symbol.st_shndx.0 as usize)
+                        .and_then(|section| section.name.to_owned());
+                }
+            }
+        }
+        Some(SectionType::Relocation(relocations))
+    }
+
     pub fn type_name(&self) -> String {
         self.sh_type.to_str()
     }
 
+    pub fn sh_type(&self) -> u32 {
+        self.sh_type.0
+    }
+
+    /// Whether the section occupies memory during process execution (SHF_ALLOC)
+    pub fn is_alloc(&self) -> bool {
+        self.flags & 0x2 != 0
+    }
+
+    /// Whether the section contains machine instructions (SHF_EXECINSTR)
+    pub fn is_executable(&self) -> bool {
+        self.flags & 0x4 != 0
+    }
+
+    /// Whether the section holds thread-local storage (SHF_TLS)
+    pub fn is_tls(&self) -> bool {
+        self.flags & 0x400 != 0
+    }
+
+    /// Whether the section occupies no space in the file (NOBITS)
+    pub fn is_nobits(&self) -> bool {
+        self.sh_type.0 == 8
+    }
+
     pub fn data(&self) -> Option<&[u8]> {
         match self.data.as_ref() {
             None => None,
             Some(data) => Some(data.get()),
         }
     }
+
+    pub fn raw(&self) -> Option<&RcSlice<u8>> {
+        self.data.as_ref()
+    }
 }
@@ -0,0 +1,360 @@
+use crate::elf::{Description, ElfHeader, Span};
+use crate::utils::{self, RcSlice, Table};
+
+pub struct NoteSection {
+    pub notes: Vec<Note>,
+}
+
+pub struct Note {
+    /// Location of the whole note (header, name and descriptor)
+    pub span: Span,
+    pub name: String,
+    pub n_type: u32,
+    pub desc: Vec<u8>,
+    is_little_endian: bool,
+    is_64_bit: bool,
+    machine: u16,
+}
+
+impl NoteSection {
+    /// Notes are aligned to 4 bytes, except in sections with an alignment of 8 (e.g. `.note.gnu.property` in 64 bit files)
+    pub fn from(data: Option<RcSlice<u8>>, alignment: u64, hdr: &ElfHeader) -> Self {
+        let is_little_endian = hdr.is_little_endian();
+        let mut notes = Vec::new();
+        let data = match data {
+            None => return Self { notes },
+            Some(data) => data,
+        };
+        let align = |offset: usize| match alignment {
+            8 => (offset + 7) & !7,
+            _ => (offset + 3) & !3,
+        };
+
+        let mut offset = 0;
+        while offset + 12 <= data.get().len() {
+            let namesz = data.read_u32(offset, is_little_endian) as usize;
+            let descsz = data.read_u32(offset + 4, is_little_endian) as usize;
+            let n_type = data.read_u32(offset + 8, is_little_endian);
+
+            let name_start = offset + 12;
+            let desc_start = align(name_start + namesz);
+            let desc_end = desc_start + descsz;
+            if desc_end > data.get().len() {
+                break;
+            }
+            let name = match utils::raw_to_str(&data.get()[name_start..name_start + namesz]).1 {
+                Ok(name) => name.to_owned(),
+                Err(_) => String::new(),
+            };
+            notes.push(Note {
+                span: Span::new(data.start + offset, desc_end - offset),
+                name,
+                n_type,
+                desc: data.get()[desc_start..desc_end].to_owned(),
+                is_little_endian,
+                is_64_bit: hdr.is_64_bit(),
+                machine: hdr.e_machine.0,
+            });
+            offset = align(desc_end);
+        }
+        Self { notes }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Owner", "Type", "Size", "Description"]);
+        for note in &self.notes {
+            table.push(vec![
+                note.name.to_owned(),
+                note.note_type().to_str(),
+                format!("0x{:x}", note.desc.len()),
+                note.describe(),
+            ]);
+        }
+        table
+    }
+}
+
+impl Note {
+    pub fn note_type(&self) -> NoteType {
+        NoteType(self.n_type, self.name.to_owned())
+    }
+
+    fn read_u32(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.desc.get(offset..offset + 4)?.try_into().ok()?;
+        Some(match self.is_little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn read_u64(&self, offset: usize) -> Option<u64> {
+        let bytes: [u8; 8] = self.desc.get(offset..offset + 8)?.try_into().ok()?;
+        Some(match self.is_little_endian {
+            true => u64::from_le_bytes(bytes),
+            false => u64::from_be_bytes(bytes),
+        })
+    }
+
+    fn read_str(&self, start: usize, end: usize) -> Option<String> {
+        let raw = self.desc.get(start..end.min(self.desc.len()))?;
+        match utils::raw_to_str(raw).1 {
+            Ok(string) => Some(string.to_owned()),
+            Err(_) => None,
+        }
+    }
+
+    /// Android API level and NDK version from an `.note.android.ident` note
+    pub fn android_ident(&self) -> Option<AndroidIdent> {
+        if self.name != "Android" || self.n_type != 1 {
+            return None;
+        }
+        Some(AndroidIdent {
+            api_level: self.read_u32(0)?,
+            // the NDK version and build number were added in NDK r14
+            ndk_version: self.read_str(4, 68).filter(|version| !version.is_empty()),
+            ndk_build_number: self.read_str(68, 132).filter(|build| !build.is_empty()),
+        })
+    }
+
+    /// Properties of an `NT_GNU_PROPERTY_TYPE_0` note, each padded to the word size of the file
+    pub fn gnu_properties(&self) -> Vec<GnuProperty> {
+        let mut properties = Vec::new();
+        if self.name != "GNU" || self.n_type != 5 {
+            return properties;
+        }
+        let align = match self.is_64_bit {
+            true => 8,
+            false => 4,
+        };
+        let mut offset = 0;
+        while let (Some(pr_type), Some(pr_datasz)) =
+            (self.read_u32(offset), self.read_u32(offset + 4))
+        {
+            let start = offset + 8;
+            let end = start + pr_datasz as usize;
+            let data = match self.desc.get(start..end) {
+                None => break,
+                Some(data) => data,
+            };
+            properties.push(GnuProperty {
+                pr_type: GnuPropertyType(pr_type, self.machine),
+                value: match pr_datasz {
+                    4 => self.read_u32(start).map(|value| value as u64),
+                    8 => self.read_u64(start),
+                    _ => None,
+                },
+                data: data.to_owned(),
+            });
+            offset = (end + align - 1) & !(align - 1);
+        }
+        properties
+    }
+
+    pub fn describe(&self) -> String {
+        match (self.name.as_str(), self.n_type) {
+            // NT_GNU_ABI_TAG
+            ("GNU", 1) => match (
+                self.read_u32(0),
+                self.read_u32(4),
+                self.read_u32(8),
+                self.read_u32(12),
+            ) {
+                (Some(os), Some(major), Some(minor), Some(subminor)) => {
+                    let os = match os {
+                        0 => "Linux".to_owned(),
+                        1 => "Hurd".to_owned(),
+                        2 => "Solaris".to_owned(),
+                        3 => "FreeBSD".to_owned(),
+                        other => format!("<unknown: 0x{:x}>", other),
+                    };
+                    format!("OS: {}, ABI: {}.{}.{}", os, major, minor, subminor)
+                }
+                _ => utils::raw_to_hex(&self.desc),
+            },
+            // NT_GNU_BUILD_ID, Go build ID
+            ("GNU", 3) => format!("Build ID: {}", hex_string(&self.desc)),
+            ("Go", 4) => format!("Build ID: {}", String::from_utf8_lossy(&self.desc)),
+            // NT_GNU_GOLD_VERSION
+            ("GNU", 4) => format!("Version: {}", String::from_utf8_lossy(&self.desc)),
+            // NT_GNU_PROPERTY_TYPE_0
+            ("GNU", 5) => self
+                .gnu_properties()
+                .iter()
+                .map(|property| property.to_string())
+                .collect::<Vec<String>>()
+                .join("; "),
+            // SystemTap probe: pc, base and semaphore addresses followed by provider, name and arguments
+            ("stapsdt", 3) => {
+                let word = match self.is_64_bit {
+                    true => 8,
+                    false => 4,
+                };
+                let strings: Vec<String> = self
+                    .desc
+                    .get(word * 3..)
+                    .unwrap_or(&[])
+                    .split(|&c| c == b'\0')
+                    .map(|string| String::from_utf8_lossy(string).into_owned())
+                    .collect();
+                let location = match self.is_64_bit {
+                    true => self.read_u64(0),
+                    false => self.read_u32(0).map(|pc| pc as u64),
+                };
+                match (location, strings.len()) {
+                    (Some(location), 3..) => format!(
+                        "Provider: {}, Name: {}, Location: 0x{:x}, Arguments: {}",
+                        strings[0], strings[1], location, strings[2]
+                    ),
+                    _ => utils::raw_to_hex(&self.desc),
+                }
+            }
+            ("Android", 1) => match self.android_ident() {
+                Some(ident) => ident.to_string(),
+                None => utils::raw_to_hex(&self.desc),
+            },
+            _ => utils::raw_to_hex(&self.desc),
+        }
+    }
+}
+
+pub struct AndroidIdent {
+    pub api_level: u32,
+    pub ndk_version: Option<String>,
+    pub ndk_build_number: Option<String>,
+}
+
+impl std::fmt::Display for AndroidIdent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "API level: {}", self.api_level)?;
+        if let Some(version) = &self.ndk_version {
+            write!(f, ", NDK: {}", version)?;
+        }
+        if let Some(build) = &self.ndk_build_number {
+            write!(f, " (build {})", build)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct GnuProperty {
+    pub pr_type: GnuPropertyType,
+    /// Value of 4 or 8 byte properties
+    pub value: Option<u64>,
+    pub data: Vec<u8>,
+}
+
+impl GnuProperty {
+    pub fn bits(&self) -> u64 {
+        self.value.unwrap_or(0)
+    }
+}
+
+impl std::fmt::Display for GnuProperty {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let value = self.bits();
+        let names: &[(u64, &str)] = match (self.pr_type.1, self.pr_type.0) {
+            // X86_FEATURE_1_AND
+            (3 | 62, 0xc0000002) => &[(1, "IBT"), (2, "SHSTK"), (4, "LAM_U48"), (8, "LAM_U57")],
+            // X86_ISA_1_USED, X86_ISA_1_NEEDED
+            (3 | 62, 0xc0010002 | 0xc0008002) => &[
+                (1, "x86-64-baseline"),
+                (2, "x86-64-v2"),
+                (4, "x86-64-v3"),
+                (8, "x86-64-v4"),
+            ],
+            // X86_FEATURE_2_USED, X86_FEATURE_2_NEEDED
+            (3 | 62, 0xc0010001 | 0xc0008001) => &[
+                (0x1, "x86"),
+                (0x2, "x87"),
+                (0x4, "MMX"),
+                (0x8, "XMM"),
+                (0x10, "YMM"),
+                (0x20, "ZMM"),
+                (0x40, "FXSR"),
+                (0x80, "XSAVE"),
+                (0x100, "XSAVEOPT"),
+                (0x200, "XSAVEC"),
+                (0x400, "TMM"),
+                (0x800, "MASK"),
+            ],
+            // AARCH64_FEATURE_1_AND
+            (183, 0xc0000000) => &[(1, "BTI"), (2, "PAC"), (4, "GCS")],
+            // 1_NEEDED
+            (_, 0xb0008000) => &[(1, "indirect external access")],
+            _ => {
+                return match (self.pr_type.0, self.value) {
+                    // NO_COPY_ON_PROTECTED
+                    (2, _) => write!(f, "{}", self.pr_type.to_str()),
+                    (_, Some(value)) => write!(f, "{}: 0x{:x}", self.pr_type.to_str(), value),
+                    (_, None) => write!(
+                        f,
+                        "{}: {}",
+                        self.pr_type.to_str(),
+                        utils::raw_to_hex(&self.data)
+                    ),
+                };
+            }
+        };
+        let mut flags: Vec<&str> = names
+            .iter()
+            .filter(|(bit, _)| value & bit != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        if flags.is_empty() {
+            flags.push("<None>");
+        }
+        write!(f, "{}: {}", self.pr_type.to_str(), flags.join(", "))
+    }
+}
+
+/// GNU property type, along with the `e_machine` of the file which determines the meaning of processor specific types
+pub struct GnuPropertyType(pub u32, pub u16);
+
+impl Description for GnuPropertyType {
+    fn to_str(&self) -> String {
+        let name = match (self.1, self.0) {
+            (_, 1) => "stack size",
+            (_, 2) => "no copy on protected",
+            (_, 0xb0008000) => "1_needed",
+            (3 | 62, 0xc0000002) => "x86 feature",
+            (3 | 62, 0xc0008001) => "x86 feature needed",
+            (3 | 62, 0xc0010001) => "x86 feature used",
+            (3 | 62, 0xc0008002) => "x86 ISA needed",
+            (3 | 62, 0xc0010002) => "x86 ISA used",
+            (183, 0xc0000000) => "AArch64 feature",
+            (183, 0xc0000001) => "AArch64 pointer authentication ABI",
+            (_, other) => return format!("<unknown: 0x{:x}>", other),
+        };
+        name.to_owned()
+    }
+}
+
+fn hex_string(raw: &[u8]) -> String {
+    raw.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Note type, along with the owner name which determines its meaning
+pub struct NoteType(pub u32, pub String);
+
+impl Description for NoteType {
+    fn to_str(&self) -> String {
+        let name = match (self.1.as_str(), self.0) {
+            ("GNU", 1) => "NT_GNU_ABI_TAG",
+            ("GNU", 2) => "NT_GNU_HWCAP",
+            ("GNU", 3) => "NT_GNU_BUILD_ID",
+            ("GNU", 4) => "NT_GNU_GOLD_VERSION",
+            ("GNU", 5) => "NT_GNU_PROPERTY_TYPE_0",
+            ("Go", 4) => "GO_BUILDID",
+            ("stapsdt", 3) => "NT_STAPSDT",
+            ("FDO", 0xcafe1a7e) => "FDO_PACKAGING_METADATA",
+            ("Android", 1) => "NT_ANDROID_TYPE_IDENT",
+            ("Android", 2) => "NT_ANDROID_TYPE_KUSER",
+            ("Android", 3) => "NT_ANDROID_TYPE_MEMTAG",
+            ("Android", 4) => "NT_ANDROID_TYPE_PAD_SEGMENT",
+            ("Linux", 0x100) => "LINUX_ELFNOTE_BUILD_SALT",
+            ("Linux", 0x101) => "LINUX_ELFNOTE_LTO_INFO",
+            (_, other) => return format!("<unknown: 0x{:x}>", other),
+        };
+        name.to_owned()
+    }
+}
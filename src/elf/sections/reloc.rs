@@ -0,0 +1,516 @@
+use super::Symbol;
+use crate::elf::{Description, ElfHeader, Span};
+use crate::utils::{self, RcSlice, Table};
+
+pub enum RelocationFormat {
+    Rel,
+    Rela,
+    /// Packed relative relocations, which only encode the relocated addresses
+    Relr,
+}
+
+pub struct RelocationSection {
+    pub format: RelocationFormat,
+    pub relocations: Vec<Relocation>,
+}
+
+pub struct Relocation {
+    pub r_offset: u64,
+    pub r_info: u64,
+    pub sym: u32,
+    pub r_type: RelocType,
+    pub addend: Option<i64>,
+    pub symbol_value: Option<u64>,
+    pub symbol_name: Option<String>,
+    /// Location of the entry the relocation was decoded from (none for Android packed relocations,
+    /// whose entries are spread over a variable length encoding)
+    pub span: Option<Span>,
+}
+
+impl RelocationSection {
+    /// Decode a REL or RELA section, resolving symbols using the linked symbol table
+    pub fn from(
+        data: Option<RcSlice<u8>>,
+        symbols: Option<&[Symbol]>,
+        format: RelocationFormat,
+        hdr: &ElfHeader,
+    ) -> Self {
+        let mut relocations = Vec::new();
+        let data = match data {
+            None => {
+                return Self {
+                    format,
+                    relocations,
+                }
+            }
+            Some(data) => data,
+        };
+        let is_little_endian = hdr.is_little_endian();
+        let is_64_bit = hdr.is_64_bit();
+        let word = match is_64_bit {
+            true => 8,
+            false => 4,
+        };
+        let entsize = match format {
+            RelocationFormat::Rela => word * 3,
+            _ => word * 2,
+        };
+
+        for i in 0..data.get().len() / entsize {
+            let temp = RcSlice::from(&data, i * entsize, (i + 1) * entsize);
+            let (r_offset, r_info) = match is_64_bit {
+                true => (
+                    temp.read_u64(0, is_little_endian),
+                    temp.read_u64(8, is_little_endian),
+                ),
+                false => (
+                    temp.read_u32(0, is_little_endian) as u64,
+                    temp.read_u32(4, is_little_endian) as u64,
+                ),
+            };
+            let addend = match (&format, is_64_bit) {
+                (RelocationFormat::Rela, true) => Some(temp.read_u64(16, is_little_endian) as i64),
+                (RelocationFormat::Rela, false) => {
+                    Some(temp.read_u32(8, is_little_endian) as i32 as i64)
+                }
+                _ => None,
+            };
+            let (sym, r_type) = Self::split_info(r_info, hdr);
+            let symbol = symbols.and_then(|symbols| symbols.get(sym as usize));
+            relocations.push(Relocation {
+                r_offset,
+                r_info,
+                sym,
+                r_type: RelocType(r_type, hdr.e_machine.0),
+                addend,
+                symbol_value: symbol.map(|symbol| symbol.st_value),
+                symbol_name: symbol
+                    .map(|symbol| symbol.name_or_empty().to_owned())
+                    .filter(|name| !name.is_empty()),
+                span: Some(Span::of(&temp)),
+            });
+        }
+        Self {
+            format,
+            relocations,
+        }
+    }
+
+    /// Expand an Android packed relocation section (`APS2` magic followed by SLEB128 values).
+    /// Relocations are stored in groups which may share the offset delta, info and addend of their members.
+    pub fn from_android(
+        data: Option<RcSlice<u8>>,
+        symbols: Option<&[Symbol]>,
+        format: RelocationFormat,
+        hdr: &ElfHeader,
+    ) -> Option<Self> {
+        const GROUPED_BY_INFO: i64 = 1;
+        const GROUPED_BY_OFFSET_DELTA: i64 = 2;
+        const GROUPED_BY_ADDEND: i64 = 4;
+        const GROUP_HAS_ADDEND: i64 = 8;
+        // groups sharing the offset delta and info read no bytes per relocation, so the count has to be bounded
+        const MAX_RELOCATIONS: i64 = 0x10_0000;
+
+        let data = data?;
+        let raw = data.get();
+        if !raw.starts_with(b"APS2") {
+            return None;
+        }
+        let mut offset = 4;
+        let mut read = || utils::read_sleb128(raw, &mut offset);
+
+        let count = read()?;
+        if !(0..=MAX_RELOCATIONS).contains(&count) {
+            return None;
+        }
+        let mut r_offset = read()? as u64;
+        let mut r_info = 0;
+        let mut addend: i64 = 0;
+        let mut relocations = Vec::new();
+        // offsets and infos are stored as signed values, so truncate them to the word size
+        let mask = match hdr.is_64_bit() {
+            true => u64::MAX,
+            false => 0xffffffff,
+        };
+
+        while (relocations.len() as i64) < count {
+            let group_size = read()?;
+            let group_flags = read()?;
+            let group_offset_delta = match group_flags & GROUPED_BY_OFFSET_DELTA {
+                0 => 0,
+                _ => read()?,
+            };
+            if group_flags & GROUPED_BY_INFO != 0 {
+                r_info = read()? as u64;
+            }
+            match (
+                group_flags & GROUP_HAS_ADDEND,
+                group_flags & GROUPED_BY_ADDEND,
+            ) {
+                (0, _) => addend = 0,
+                (_, 0) => (),
+                _ => addend = addend.wrapping_add(read()?),
+            }
+
+            // protect against corrupted group sizes
+            if group_size < 0 || group_size > count - relocations.len() as i64 {
+                return None;
+            }
+            for _ in 0..group_size {
+                r_offset = match group_flags & GROUPED_BY_OFFSET_DELTA {
+                    0 => r_offset.wrapping_add(read()? as u64),
+                    _ => r_offset.wrapping_add(group_offset_delta as u64),
+                };
+                if group_flags & GROUPED_BY_INFO == 0 {
+                    r_info = read()? as u64;
+                }
+                if group_flags & GROUP_HAS_ADDEND != 0 && group_flags & GROUPED_BY_ADDEND == 0 {
+                    addend = addend.wrapping_add(read()?);
+                }
+                r_offset &= mask;
+                r_info &= mask;
+                let (sym, r_type) = Self::split_info(r_info, hdr);
+                let symbol = symbols.and_then(|symbols| symbols.get(sym as usize));
+                relocations.push(Relocation {
+                    r_offset,
+                    r_info,
+                    sym,
+                    r_type: RelocType(r_type, hdr.e_machine.0),
+                    addend: match format {
+                        RelocationFormat::Rela => Some(addend),
+                        _ => None,
+                    },
+                    symbol_value: symbol.map(|symbol| symbol.st_value),
+                    symbol_name: symbol
+                        .map(|symbol| symbol.name_or_empty().to_owned())
+                        .filter(|name| !name.is_empty()),
+                    span: None,
+                });
+            }
+        }
+        Some(Self {
+            format,
+            relocations,
+        })
+    }
+
+    /// Expand a RELR section. Each entry is either an address (even) which is relocated,
+    /// or a bitmap (odd) whose remaining bits mark which of the following words are relocated.
+    pub fn from_relr(data: Option<RcSlice<u8>>, hdr: &ElfHeader) -> Self {
+        let format = RelocationFormat::Relr;
+        let mut relocations = Vec::new();
+        let data = match data {
+            None => {
+                return Self {
+                    format,
+                    relocations,
+                }
+            }
+            Some(data) => data,
+        };
+        let is_little_endian = hdr.is_little_endian();
+        let word = match hdr.is_64_bit() {
+            true => 8,
+            false => 4,
+        };
+        let r_type = RelocType(RelocType::relative(hdr.e_machine.0), hdr.e_machine.0);
+
+        // crafted entries may wrap around the address space, which mustn't stop parsing
+        let mut next: u64 = 0;
+        for i in 0..data.get().len() / word {
+            let entry = match hdr.is_64_bit() {
+                true => data.read_u64(i * word, is_little_endian),
+                false => data.read_u32(i * word, is_little_endian) as u64,
+            };
+            let mut push = |r_offset: u64| {
+                relocations.push(Relocation {
+                    r_offset,
+                    r_info: entry,
+                    sym: 0,
+                    r_type: RelocType(r_type.0, r_type.1),
+                    addend: None,
+                    symbol_value: None,
+                    symbol_name: None,
+                    span: Some(Span::new(data.start + i * word, word)),
+                })
+            };
+            match entry & 1 {
+                0 => {
+                    push(entry);
+                    next = entry.wrapping_add(word as u64);
+                }
+                _ => {
+                    let bits = word as u64 * 8 - 1;
+                    for bit in 0..bits {
+                        if (entry >> (bit + 1)) & 1 != 0 {
+                            push(next.wrapping_add(bit * word as u64));
+                        }
+                    }
+                    next = next.wrapping_add(bits * word as u64);
+                }
+            }
+        }
+        Self {
+            format,
+            relocations,
+        }
+    }
+
+    // 64 bit MIPS uses a different r_info layout (32 bit symbol, special symbol and 3 one byte types)
+    fn split_info(r_info: u64, hdr: &ElfHeader) -> (u32, u32) {
+        match (hdr.is_64_bit(), hdr.e_machine.0) {
+            (true, 8) => {
+                let raw = match hdr.is_little_endian() {
+                    true => r_info.to_le_bytes(),
+                    false => r_info.to_be_bytes(),
+                };
+                let sym = match hdr.is_little_endian() {
+                    true => u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]),
+                    false => u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]]),
+                };
+                (sym, raw[7] as u32)
+            }
+            (true, _) => ((r_info >> 32) as u32, r_info as u32),
+            (false, _) => ((r_info >> 8) as u32, (r_info & 0xff) as u32),
+        }
+    }
+
+    pub fn to_table(&self) -> Table {
+        match self.format {
+            RelocationFormat::Relr => {
+                let mut table = Table::new(&["Offset", "Entry", "Type"]);
+                for reloc in &self.relocations {
+                    table.push(vec![
+                        format!("0x{:x}", reloc.r_offset),
+                        format!("0x{:x}", reloc.r_info),
+                        reloc.r_type.to_str(),
+                    ]);
+                }
+                table
+            }
+            _ => {
+                let mut table =
+                    Table::new(&["Offset", "Info", "Type", "Sym. Value", "Sym. Name + Addend"]);
+                for reloc in &self.relocations {
+                    let name = reloc.symbol_name.clone().unwrap_or_default();
+                    let name = match reloc.addend {
+                        None => name,
+                        Some(addend) if addend < 0 => format!("{} - 0x{:x}", name, -addend),
+                        Some(addend) => format!("{} + 0x{:x}", name, addend),
+                    };
+                    table.push(vec![
+                        format!("0x{:x}", reloc.r_offset),
+                        format!("0x{:x}", reloc.r_info),
+                        reloc.r_type.to_str(),
+                        match (reloc.sym, reloc.symbol_value) {
+                            (0, _) | (_, None) => "".to_owned(),
+                            (_, Some(value)) => format!("0x{:x}", value),
+                        },
+                        name,
+                    ]);
+                }
+                table
+            }
+        }
+    }
+}
+
+/// Relocation type, along with the `e_machine` of the file which determines its meaning
+pub struct RelocType(pub u32, pub u16);
+
+impl RelocType {
+    /// The relative relocation type of an architecture, which RELR entries are equivalent to
+    pub fn relative(machine: u16) -> u32 {
+        match machine {
+            // i386, x86-64
+            3 | 62 => 8,
+            // MIPS (REL32)
+            8 => 3,
+            // PPC
+            20 => 22,
+            // PPC64
+            21 => 22,
+            // s390
+            22 => 12,
+            // ARM
+            40 => 23,
+            // AArch64
+            183 => 1027,
+            // RISC-V, LoongArch
+            243 | 258 => 3,
+            _ => 0,
+        }
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        let name = match (self.1, self.0) {
+            (_, 0) => "NONE",
+            // i386
+            (3, 1) => "386_32",
+            (3, 2) => "386_PC32",
+            (3, 3) => "386_GOT32",
+            (3, 4) => "386_PLT32",
+            (3, 5) => "386_COPY",
+            (3, 6) => "386_GLOB_DAT",
+            (3, 7) => "386_JUMP_SLOT",
+            (3, 8) => "386_RELATIVE",
+            (3, 9) => "386_GOTOFF",
+            (3, 10) => "386_GOTPC",
+            (3, 14) => "386_TLS_TPOFF",
+            (3, 35) => "386_TLS_DTPMOD32",
+            (3, 36) => "386_TLS_DTPOFF32",
+            (3, 37) => "386_TLS_TPOFF32",
+            (3, 42) => "386_IRELATIVE",
+            (3, 43) => "386_GOT32X",
+            // x86-64
+            (62, 1) => "X86_64_64",
+            (62, 2) => "X86_64_PC32",
+            (62, 3) => "X86_64_GOT32",
+            (62, 4) => "X86_64_PLT32",
+            (62, 5) => "X86_64_COPY",
+            (62, 6) => "X86_64_GLOB_DAT",
+            (62, 7) => "X86_64_JUMP_SLOT",
+            (62, 8) => "X86_64_RELATIVE",
+            (62, 9) => "X86_64_GOTPCREL",
+            (62, 10) => "X86_64_32",
+            (62, 11) => "X86_64_32S",
+            (62, 12) => "X86_64_16",
+            (62, 13) => "X86_64_PC16",
+            (62, 14) => "X86_64_8",
+            (62, 15) => "X86_64_PC8",
+            (62, 16) => "X86_64_DTPMOD64",
+            (62, 17) => "X86_64_DTPOFF64",
+            (62, 18) => "X86_64_TPOFF64",
+            (62, 19) => "X86_64_TLSGD",
+            (62, 20) => "X86_64_TLSLD",
+            (62, 21) => "X86_64_DTPOFF32",
+            (62, 22) => "X86_64_GOTTPOFF",
+            (62, 23) => "X86_64_TPOFF32",
+            (62, 24) => "X86_64_PC64",
+            (62, 25) => "X86_64_GOTOFF64",
+            (62, 26) => "X86_64_GOTPC32",
+            (62, 32) => "X86_64_SIZE32",
+            (62, 33) => "X86_64_SIZE64",
+            (62, 34) => "X86_64_GOTPC32_TLSDESC",
+            (62, 35) => "X86_64_TLSDESC_CALL",
+            (62, 36) => "X86_64_TLSDESC",
+            (62, 37) => "X86_64_IRELATIVE",
+            (62, 41) => "X86_64_GOTPCRELX",
+            (62, 42) => "X86_64_REX_GOTPCRELX",
+            // MIPS
+            (8, 2) => "MIPS_32",
+            (8, 3) => "MIPS_REL32",
+            (8, 4) => "MIPS_26",
+            (8, 5) => "MIPS_HI16",
+            (8, 6) => "MIPS_LO16",
+            (8, 7) => "MIPS_GPREL16",
+            (8, 9) => "MIPS_GOT16",
+            (8, 10) => "MIPS_PC16",
+            (8, 11) => "MIPS_CALL16",
+            (8, 18) => "MIPS_64",
+            (8, 38) => "MIPS_TLS_DTPMOD32",
+            (8, 39) => "MIPS_TLS_DTPREL32",
+            (8, 40) => "MIPS_TLS_DTPMOD64",
+            (8, 41) => "MIPS_TLS_DTPREL64",
+            (8, 47) => "MIPS_TLS_TPREL32",
+            (8, 48) => "MIPS_TLS_TPREL64",
+            (8, 126) => "MIPS_COPY",
+            (8, 127) => "MIPS_JUMP_SLOT",
+            // PPC64
+            (21, 1) => "PPC64_ADDR32",
+            (21, 19) => "PPC64_COPY",
+            (21, 20) => "PPC64_GLOB_DAT",
+            (21, 21) => "PPC64_JMP_SLOT",
+            (21, 22) => "PPC64_RELATIVE",
+            (21, 38) => "PPC64_ADDR64",
+            (21, 68) => "PPC64_DTPMOD64",
+            (21, 73) => "PPC64_TPREL64",
+            (21, 78) => "PPC64_DTPREL64",
+            (21, 248) => "PPC64_IRELATIVE",
+            // ARM
+            (40, 1) => "ARM_PC24",
+            (40, 2) => "ARM_ABS32",
+            (40, 3) => "ARM_REL32",
+            (40, 10) => "ARM_THM_CALL",
+            (40, 17) => "ARM_TLS_DTPMOD32",
+            (40, 18) => "ARM_TLS_DTPOFF32",
+            (40, 19) => "ARM_TLS_TPOFF32",
+            (40, 20) => "ARM_COPY",
+            (40, 21) => "ARM_GLOB_DAT",
+            (40, 22) => "ARM_JUMP_SLOT",
+            (40, 23) => "ARM_RELATIVE",
+            (40, 28) => "ARM_CALL",
+            (40, 29) => "ARM_JUMP24",
+            (40, 30) => "ARM_THM_JUMP24",
+            (40, 42) => "ARM_PREL31",
+            (40, 43) => "ARM_MOVW_ABS_NC",
+            (40, 44) => "ARM_MOVT_ABS",
+            (40, 47) => "ARM_THM_MOVW_ABS_NC",
+            (40, 48) => "ARM_THM_MOVT_ABS",
+            (40, 160) => "ARM_IRELATIVE",
+            // AArch64
+            (183, 257) => "AARCH64_ABS64",
+            (183, 258) => "AARCH64_ABS32",
+            (183, 261) => "AARCH64_PREL32",
+            (183, 275) => "AARCH64_ADR_PREL_PG_HI21",
+            (183, 277) => "AARCH64_ADD_ABS_LO12_NC",
+            (183, 282) => "AARCH64_JUMP26",
+            (183, 283) => "AARCH64_CALL26",
+            (183, 286) => "AARCH64_LDST64_ABS_LO12_NC",
+            (183, 311) => "AARCH64_ADR_GOT_PAGE",
+            (183, 312) => "AARCH64_LD64_GOT_LO12_NC",
+            (183, 1024) => "AARCH64_COPY",
+            (183, 1025) => "AARCH64_GLOB_DAT",
+            (183, 1026) => "AARCH64_JUMP_SLOT",
+            (183, 1027) => "AARCH64_RELATIVE",
+            (183, 1028) => "AARCH64_TLS_DTPMOD",
+            (183, 1029) => "AARCH64_TLS_DTPREL",
+            (183, 1030) => "AARCH64_TLS_TPREL",
+            (183, 1031) => "AARCH64_TLSDESC",
+            (183, 1032) => "AARCH64_IRELATIVE",
+            // RISC-V
+            (243, 1) => "RISCV_32",
+            (243, 2) => "RISCV_64",
+            (243, 3) => "RISCV_RELATIVE",
+            (243, 4) => "RISCV_COPY",
+            (243, 5) => "RISCV_JUMP_SLOT",
+            (243, 6) => "RISCV_TLS_DTPMOD32",
+            (243, 7) => "RISCV_TLS_DTPMOD64",
+            (243, 8) => "RISCV_TLS_DTPREL32",
+            (243, 9) => "RISCV_TLS_DTPREL64",
+            (243, 10) => "RISCV_TLS_TPREL32",
+            (243, 11) => "RISCV_TLS_TPREL64",
+            (243, 16) => "RISCV_BRANCH",
+            (243, 17) => "RISCV_JAL",
+            (243, 18) => "RISCV_CALL",
+            (243, 19) => "RISCV_CALL_PLT",
+            (243, 20) => "RISCV_GOT_HI20",
+            (243, 23) => "RISCV_PCREL_HI20",
+            (243, 24) => "RISCV_PCREL_LO12_I",
+            (243, 25) => "RISCV_PCREL_LO12_S",
+            (243, 26) => "RISCV_HI20",
+            (243, 27) => "RISCV_LO12_I",
+            (243, 28) => "RISCV_LO12_S",
+            (243, 51) => "RISCV_RELAX",
+            (243, 58) => "RISCV_IRELATIVE",
+            // LoongArch
+            (258, 1) => "LARCH_32",
+            (258, 2) => "LARCH_64",
+            (258, 3) => "LARCH_RELATIVE",
+            (258, 4) => "LARCH_COPY",
+            (258, 5) => "LARCH_JUMP_SLOT",
+            (258, 12) => "LARCH_IRELATIVE",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+impl Description for RelocType {
+    fn to_str(&self) -> String {
+        match self.name() {
+            Some(name) => format!("R_{}", name),
+            None => format!("<unknown: 0x{:x}>", self.0),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::elf::{Description, ElfHeader, Span};
+use crate::utils::{self, RcSlice};
+
+const SYM32_SIZE: usize = 16;
+const SYM64_SIZE: usize = 24;
+
+pub struct SymtabSection {
+    pub symbols: Vec<Symbol>,
+}
+
+impl SymtabSection {
+    pub fn from(
+        data: Option<RcSlice<u8>>,
+        strtab: Option<RcSlice<u8>>,
+        entsize: usize,
+        hdr: &ElfHeader,
+    ) -> Self {
+        let data = match data {
+            None => {
+                return Self {
+                    symbols: Vec::new(),
+                }
+            }
+            Some(data) => data,
+        };
+        let is_little_endian = hdr.is_little_endian();
+        let is_64_bit = hdr.is_64_bit();
+
+        // fall back to the native symbol size if the header doesn't specify a usable one
+        let min_size = match is_64_bit {
+            true => SYM64_SIZE,
+            false => SYM32_SIZE,
+        };
+        let entsize = match entsize < min_size {
+            true => min_size,
+            false => entsize,
+        };
+
+        let len = data.get().len();
+        let mut symbols = Vec::with_capacity(len / entsize);
+        for (index, offset) in (0..len / entsize).map(|i| (i, i * entsize)) {
+            let temp = RcSlice::from(&data, offset, offset + entsize);
+            let st_name = temp.read_u32(0, is_little_endian);
+            let (st_value, st_size, st_info, st_other, st_shndx) = match is_64_bit {
+                true => (
+                    temp.read_u64(8, is_little_endian),
+                    temp.read_u64(16, is_little_endian),
+                    temp.get()[4],
+                    temp.get()[5],
+                    temp.read_u16(6, is_little_endian),
+                ),
+                false => (
+                    temp.read_u32(4, is_little_endian) as u64,
+                    temp.read_u32(8, is_little_endian) as u64,
+                    temp.get()[12],
+                    temp.get()[13],
+                    temp.read_u16(14, is_little_endian),
+                ),
+            };
+            let name = match &strtab {
+                Some(strtab) if (st_name as usize) < strtab.get().len() => {
+                    match utils::raw_to_str(&strtab.get()[st_name as usize..]).1 {
+                        Ok(name) => Some(name.to_owned()),
+                        Err(_) => None,
+                    }
+                }
+                _ => None,
+            };
+            symbols.push(Symbol {
+                index,
+                span: Span::of(&temp),
+                name,
+                st_name,
+                st_value,
+                st_size,
+                st_info,
+                st_other,
+                st_shndx: SymShndx(st_shndx),
+            });
+        }
+        Self { symbols }
+    }
+}
+
+pub struct Symbol {
+    pub index: usize,
+    /// Location of the symbol table entry
+    pub span: Span,
+    pub name: Option<String>,
+    pub st_name: u32,
+    pub st_value: u64,
+    pub st_size: u64,
+    pub st_info: u8,
+    pub st_other: u8,
+    pub st_shndx: SymShndx,
+}
+
+impl Symbol {
+    pub fn bind(&self) -> SymBind {
+        SymBind(self.st_info >> 4)
+    }
+
+    pub fn sym_type(&self) -> SymType {
+        SymType(self.st_info & 0xf)
+    }
+
+    pub fn visibility(&self) -> SymVisibility {
+        SymVisibility(self.st_other & 0x3)
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        self.st_shndx.0 == 0
+    }
+
+    pub fn name_or_empty(&self) -> &str {
+        match &self.name {
+            Some(name) => name,
+            None => "",
+        }
+    }
+}
+
+pub struct SymBind(pub u8);
+
+impl Description for SymBind {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "LOCAL".to_owned(),
+            1 => "GLOBAL".to_owned(),
+            2 => "WEAK".to_owned(),
+            10 => "GNU_UNIQUE".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct SymType(pub u8);
+
+impl Description for SymType {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "NOTYPE".to_owned(),
+            1 => "OBJECT".to_owned(),
+            2 => "FUNC".to_owned(),
+            3 => "SECTION".to_owned(),
+            4 => "FILE".to_owned(),
+            5 => "COMMON".to_owned(),
+            6 => "TLS".to_owned(),
+            10 => "GNU_IFUNC".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct SymVisibility(pub u8);
+
+impl Description for SymVisibility {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "DEFAULT".to_owned(),
+            1 => "INTERNAL".to_owned(),
+            2 => "HIDDEN".to_owned(),
+            3 => "PROTECTED".to_owned(),
+            other => format!("<unknown: 0x{:x}>", other),
+        }
+    }
+}
+
+pub struct SymShndx(pub u16);
+
+impl Description for SymShndx {
+    fn to_str(&self) -> String {
+        match self.0 {
+            0 => "UND".to_owned(),
+            0xfff1 => "ABS".to_owned(),
+            0xfff2 => "COMMON".to_owned(),
+            0xffff => "XINDEX".to_owned(),
+            other => format!("{}", other),
+        }
+    }
+}
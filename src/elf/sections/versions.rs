@@ -0,0 +1,389 @@
+use super::{SectionType, Sections};
+use crate::elf::{Description, ElfHeader, Span};
+use crate::utils::{self, RcSlice, Table};
+
+const VERNEED_SIZE: usize = 16;
+const VERNAUX_SIZE: usize = 16;
+const VERDEF_SIZE: usize = 20;
+const VERDAUX_SIZE: usize = 8;
+
+// special version indexes
+const VER_NDX_LOCAL: u16 = 0;
+const VER_NDX_GLOBAL: u16 = 1;
+const VERSYM_HIDDEN: u16 = 0x8000;
+
+fn read_string(strtab: &Option<RcSlice<u8>>, offset: u32) -> Option<String> {
+    match strtab {
+        Some(strtab) if (offset as usize) < strtab.get().len() => {
+            match utils::raw_to_str(&strtab.get()[offset as usize..]).1 {
+                Ok(string) => Some(string.to_owned()),
+                Err(_) => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Symbol version table (`.gnu.version`), holding the version index of every dynamic symbol
+pub struct VersymSection {
+    pub versions: Vec<u16>,
+}
+
+impl VersymSection {
+    pub fn from(data: Option<RcSlice<u8>>, hdr: &ElfHeader) -> Self {
+        let versions = match data {
+            None => Vec::new(),
+            Some(data) => (0..data.get().len() / 2)
+                .map(|i| data.read_u16(i * 2, hdr.is_little_endian()))
+                .collect(),
+        };
+        Self { versions }
+    }
+
+    pub fn to_table(&self, sections: &Sections) -> Table {
+        let mut table = Table::new(&["Index", "Value", "Symbol", "Version"]);
+        let symbols = sections.dynsym().unwrap_or(&[]);
+        for (index, value) in self.versions.iter().enumerate() {
+            let version = match *value & !VERSYM_HIDDEN {
+                VER_NDX_LOCAL => String::from("*local*"),
+                VER_NDX_GLOBAL => String::from("*global*"),
+                _ => match sections.symbol_version(index) {
+                    Some(version) => version.to_string(),
+                    None => format!("<unknown: 0x{:x}>", value),
+                },
+            };
+            table.push(vec![
+                format!("{}", index),
+                format!("0x{:x}", value),
+                symbols
+                    .get(index)
+                    .map(|symbol| symbol.name_or_empty().to_owned())
+                    .unwrap_or_default(),
+                version,
+            ]);
+        }
+        table
+    }
+}
+
+/// Version requirements (`.gnu.version_r`), grouped by the library which must provide them
+pub struct VerneedSection {
+    pub files: Vec<VerneedFile>,
+}
+
+pub struct VerneedFile {
+    /// Location of the `Elf_Verneed` entry
+    pub span: Span,
+    pub vn_version: u16,
+    /// Name of the required library, which matches a `DT_NEEDED` entry
+    pub file: Option<String>,
+    pub versions: Vec<Vernaux>,
+}
+
+pub struct Vernaux {
+    /// Location of the `Elf_Vernaux` entry
+    pub span: Span,
+    pub vna_hash: u32,
+    pub vna_flags: VerFlags,
+    /// Version index used in `.gnu.version`
+    pub vna_other: u16,
+    pub name: Option<String>,
+}
+
+impl VerneedSection {
+    pub fn from(
+        data: Option<RcSlice<u8>>,
+        strtab: Option<RcSlice<u8>>,
+        count: u32,
+        hdr: &ElfHeader,
+    ) -> Self {
+        let mut files = Vec::new();
+        let data = match data {
+            None => return Self { files },
+            Some(data) => data,
+        };
+        let le = hdr.is_little_endian();
+        let len = data.get().len();
+
+        // entries are chained by relative offsets, sh_info holds the number of entries
+        let mut offset = 0;
+        for _ in 0..count {
+            if offset + VERNEED_SIZE > len {
+                break;
+            }
+            let entry = RcSlice::from(&data, offset, offset + VERNEED_SIZE);
+            let vn_cnt = entry.read_u16(2, le);
+            let vn_file = entry.read_u32(4, le);
+            let vn_aux = entry.read_u32(8, le) as usize;
+            let vn_next = entry.read_u32(12, le) as usize;
+
+            let mut versions = Vec::new();
+            let mut aux_offset = offset + vn_aux;
+            for _ in 0..vn_cnt {
+                if aux_offset + VERNAUX_SIZE > len {
+                    break;
+                }
+                let aux = RcSlice::from(&data, aux_offset, aux_offset + VERNAUX_SIZE);
+                let vna_next = aux.read_u32(12, le) as usize;
+                versions.push(Vernaux {
+                    span: Span::of(&aux),
+                    vna_hash: aux.read_u32(0, le),
+                    vna_flags: VerFlags(aux.read_u16(4, le)),
+                    vna_other: aux.read_u16(6, le),
+                    name: read_string(&strtab, aux.read_u32(8, le)),
+                });
+                if vna_next == 0 {
+                    break;
+                }
+                aux_offset += vna_next;
+            }
+
+            files.push(VerneedFile {
+                span: Span::of(&entry),
+                vn_version: entry.read_u16(0, le),
+                file: read_string(&strtab, vn_file),
+                versions,
+            });
+            if vn_next == 0 {
+                break;
+            }
+            offset += vn_next;
+        }
+        Self { files }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["File", "Version", "Hash", "Flags", "Index"]);
+        for file in &self.files {
+            for version in &file.versions {
+                table.push(vec![
+                    file.file.clone().unwrap_or_default(),
+                    version.name.clone().unwrap_or_default(),
+                    format!("0x{:08x}", version.vna_hash),
+                    version.vna_flags.to_str(),
+                    format!("{}", version.vna_other),
+                ]);
+            }
+        }
+        table
+    }
+}
+
+/// Version definitions (`.gnu.version_d`) of the versions provided by the file
+pub struct VerdefSection {
+    pub definitions: Vec<Verdef>,
+}
+
+pub struct Verdef {
+    /// Location of the `Elf_Verdef` entry
+    pub span: Span,
+    pub vd_version: u16,
+    pub vd_flags: VerFlags,
+    /// Version index used in `.gnu.version`
+    pub vd_ndx: u16,
+    pub vd_hash: u32,
+    /// The name of the version, followed by the names of the versions it inherits from
+    pub names: Vec<String>,
+}
+
+impl Verdef {
+    pub fn name(&self) -> Option<&str> {
+        self.names.first().map(|name| name.as_str())
+    }
+}
+
+impl VerdefSection {
+    pub fn from(
+        data: Option<RcSlice<u8>>,
+        strtab: Option<RcSlice<u8>>,
+        count: u32,
+        hdr: &ElfHeader,
+    ) -> Self {
+        let mut definitions = Vec::new();
+        let data = match data {
+            None => return Self { definitions },
+            Some(data) => data,
+        };
+        let le = hdr.is_little_endian();
+        let len = data.get().len();
+
+        let mut offset = 0;
+        for _ in 0..count {
+            if offset + VERDEF_SIZE > len {
+                break;
+            }
+            let entry = RcSlice::from(&data, offset, offset + VERDEF_SIZE);
+            let vd_cnt = entry.read_u16(6, le);
+            let vd_aux = entry.read_u32(12, le) as usize;
+            let vd_next = entry.read_u32(16, le) as usize;
+
+            let mut names = Vec::new();
+            let mut aux_offset = offset + vd_aux;
+            for _ in 0..vd_cnt {
+                if aux_offset + VERDAUX_SIZE > len {
+                    break;
+                }
+                let aux = RcSlice::from(&data, aux_offset, aux_offset + VERDAUX_SIZE);
+                names.push(read_string(&strtab, aux.read_u32(0, le)).unwrap_or_default());
+                let vda_next = aux.read_u32(4, le) as usize;
+                if vda_next == 0 {
+                    break;
+                }
+                aux_offset += vda_next;
+            }
+
+            definitions.push(Verdef {
+                span: Span::of(&entry),
+                vd_version: entry.read_u16(0, le),
+                vd_flags: VerFlags(entry.read_u16(2, le)),
+                vd_ndx: entry.read_u16(4, le),
+                vd_hash: entry.read_u32(8, le),
+                names,
+            });
+            if vd_next == 0 {
+                break;
+            }
+            offset += vd_next;
+        }
+        Self { definitions }
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&["Index", "Flags", "Hash", "Name", "Parents"]);
+        for definition in &self.definitions {
+            table.push(vec![
+                format!("{}", definition.vd_ndx),
+                definition.vd_flags.to_str(),
+                format!("0x{:08x}", definition.vd_hash),
+                definition.name().unwrap_or_default().to_owned(),
+                definition
+                    .names
+                    .iter()
+                    .skip(1)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ]);
+        }
+        table
+    }
+}
+
+pub struct VerFlags(pub u16);
+
+impl Description for VerFlags {
+    fn to_str(&self) -> String {
+        let mut names = Vec::new();
+        for (bit, name) in [(0x1, "BASE"), (0x2, "WEAK"), (0x4, "INFO")] {
+            if self.0 & bit != 0 {
+                names.push(name.to_owned());
+            }
+        }
+        let unknown = self.0 & !0x7;
+        if unknown != 0 {
+            names.push(format!("<unknown: 0x{:x}>", unknown));
+        }
+        match names.is_empty() {
+            true => String::from("none"),
+            false => names.join(" | "),
+        }
+    }
+}
+
+/// The version attached to a dynamic symbol
+pub struct SymbolVersion {
+    pub name: String,
+    /// Library the version is required from, None for versions defined by the file itself
+    pub file: Option<String>,
+    /// Hidden versions can't be used to link new objects against the symbol (`name@VERSION` instead of `name@@VERSION`)
+    pub is_hidden: bool,
+    /// The requirement is weak (VER_FLG_WEAK)
+    pub is_weak: bool,
+}
+
+impl std::fmt::Display for SymbolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.file, self.is_hidden) {
+            (Some(file), _) => write!(f, "{} ({})", self.name, file),
+            (None, true) => write!(f, "{} (hidden)", self.name),
+            (None, false) => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl Sections {
+    /// Symbols of the dynamic symbol table (DYNSYM), which `.gnu.version` entries correspond to
+    pub fn dynsym(&self) -> Option<&[super::Symbol]> {
+        self.0
+            .iter()
+            .find(|section| section.sh_type() == 11)
+            .and_then(|section| match &section.section_type {
+                SectionType::Symtab(symtab) => Some(&symtab.symbols[..]),
+                _ => None,
+            })
+    }
+
+    pub fn versym(&self) -> Option<&VersymSection> {
+        self.0
+            .iter()
+            .find_map(|section| match &section.section_type {
+                SectionType::Versym(versym) => Some(versym),
+                _ => None,
+            })
+    }
+
+    pub fn verneed(&self) -> Option<&VerneedSection> {
+        self.0
+            .iter()
+            .find_map(|section| match &section.section_type {
+                SectionType::Verneed(verneed) => Some(verneed),
+                _ => None,
+            })
+    }
+
+    pub fn verdef(&self) -> Option<&VerdefSection> {
+        self.0
+            .iter()
+            .find_map(|section| match &section.section_type {
+                SectionType::Verdef(verdef) => Some(verdef),
+                _ => None,
+            })
+    }
+
+    /// Version of the dynamic symbol at `index`, None for unversioned (local or global) symbols
+    pub fn symbol_version(&self, index: usize) -> Option<SymbolVersion> {
+        let value = *self.versym()?.versions.get(index)?;
+        let is_hidden = value & VERSYM_HIDDEN != 0;
+        let version = value & !VERSYM_HIDDEN;
+        if version == VER_NDX_LOCAL || version == VER_NDX_GLOBAL {
+            return None;
+        }
+
+        // a version index is either defined by the file or required from a library
+        if let Some(verdef) = self.verdef() {
+            if let Some(definition) = verdef
+                .definitions
+                .iter()
+                .find(|definition| definition.vd_ndx == version)
+            {
+                return Some(SymbolVersion {
+                    name: definition.name().unwrap_or_default().to_owned(),
+                    file: None,
+                    is_hidden,
+                    is_weak: false,
+                });
+            }
+        }
+        self.verneed()?.files.iter().find_map(|file| {
+            file.versions
+                .iter()
+                .find(|aux| aux.vna_other == version)
+                .map(|aux| SymbolVersion {
+                    name: aux.name.clone().unwrap_or_default(),
+                    file: file.file.clone(),
+                    is_hidden,
+                    is_weak: aux.vna_flags.0 & 0x2 != 0,
+                })
+        })
+    }
+}
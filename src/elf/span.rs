@@ -0,0 +1,55 @@
+use crate::utils::RcSlice;
+
+/// Location of a parsed value in the file, given as an absolute offset and a size in bytes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl Span {
+    pub fn new(offset: usize, size: usize) -> Self {
+        Self { offset, size }
+    }
+
+    /// Span covering a whole slice of the file
+    pub fn of(raw: &RcSlice<u8>) -> Self {
+        Self {
+            offset: raw.start,
+            size: raw.end - raw.start,
+        }
+    }
+
+    pub fn end(&self) -> usize {
+        self.offset + self.size
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        offset >= self.offset && offset < self.end()
+    }
+}
+
+/// Location of a named field of a header
+pub struct FieldSpan {
+    pub name: &'static str,
+    pub span: Span,
+}
+
+/// Build the spans of a structure located at `base`, given the offset and size of each field in it
+pub(super) fn layout(base: usize, fields: &[(&'static str, usize, usize)]) -> Vec<FieldSpan> {
+    fields
+        .iter()
+        .map(|&(name, offset, size)| FieldSpan {
+            name,
+            span: Span::new(base + offset, size),
+        })
+        .collect()
+}
+
+/// Find the span of a field by name
+pub fn span_of(spans: &[FieldSpan], name: &str) -> Option<Span> {
+    spans
+        .iter()
+        .find(|field| field.name == name)
+        .map(|field| field.span)
+}
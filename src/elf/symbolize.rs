@@ -0,0 +1,165 @@
+use super::sections::{Sections, Symbol};
+use super::Elf;
+use std::fmt;
+
+// symbol types
+const STT_NOTYPE: u8 = 0;
+const STT_SECTION: u8 = 3;
+const STT_FILE: u8 = 4;
+const STT_TLS: u8 = 6;
+
+// symbol binds
+const STB_LOCAL: u8 = 0;
+
+// special section indexes
+const SHN_UNDEF: u16 = 0;
+const SHN_LORESERVE: u16 = 0xff00;
+
+// executable types
+const ET_REL: u16 = 1;
+
+struct IndexedSymbol {
+    address: u64,
+    size: u64,
+    name: String,
+    shndx: u16,
+    /// Lower is better when several symbols share an address
+    rank: u8,
+}
+
+/// A symbol found for an address
+pub struct SymbolMatch<'a> {
+    pub name: &'a str,
+    pub offset: u64,
+    /// The address lies within `st_size` bytes of the symbol
+    pub is_covering: bool,
+    pub shndx: u16,
+}
+
+impl fmt::Display for SymbolMatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.offset {
+            0 => write!(f, "{}", self.name),
+            offset => write!(f, "{}+0x{:x}", self.name, offset),
+        }
+    }
+}
+
+/// Defined symbols of `.symtab` and `.dynsym` sorted by address, for address to symbol lookups
+pub struct SymbolIndex {
+    symbols: Vec<IndexedSymbol>,
+    /// Largest `st_size`, which bounds how far back a covering symbol can start
+    max_size: u64,
+}
+
+impl SymbolIndex {
+    pub fn from(sections: &Sections) -> Self {
+        let mut symbols: Vec<IndexedSymbol> = sections
+            .symbols()
+            .filter(|symbol| Self::is_indexed(symbol))
+            .map(|symbol| IndexedSymbol {
+                address: symbol.st_value,
+                size: symbol.st_size,
+                name: symbol.name_or_empty().to_owned(),
+                shndx: symbol.st_shndx.0,
+                rank: Self::rank(symbol),
+            })
+            .collect();
+        // prefer public names (`malloc`) over internal aliases (`__libc_malloc`)
+        symbols.sort_by_key(|symbol| {
+            let underscores = symbol.name.bytes().take_while(|byte| *byte == b'_').count();
+            (
+                symbol.address,
+                symbol.rank,
+                underscores,
+                symbol.name.clone(),
+                std::cmp::Reverse(symbol.size),
+            )
+        });
+        // the same symbol usually appears in both .dynsym and .symtab
+        symbols.dedup_by(|b, a| a.address == b.address && a.name == b.name);
+        let max_size = symbols.iter().map(|symbol| symbol.size).max().unwrap_or(0);
+        Self { symbols, max_size }
+    }
+
+    fn is_indexed(symbol: &Symbol) -> bool {
+        let sym_type = symbol.sym_type().0;
+        let shndx = symbol.st_shndx.0;
+        !symbol.name_or_empty().is_empty()
+            && shndx != SHN_UNDEF
+            && shndx < SHN_LORESERVE
+            && !matches!(sym_type, STT_SECTION | STT_FILE | STT_TLS)
+    }
+
+    /// Prefer typed over NOTYPE symbols (e.g. mapping symbols like `$x`), then global over local ones
+    fn rank(symbol: &Symbol) -> u8 {
+        let typed = match symbol.sym_type().0 == STT_NOTYPE {
+            true => 2,
+            false => 0,
+        };
+        let local = match symbol.bind().0 == STB_LOCAL {
+            true => 1,
+            false => 0,
+        };
+        typed + local
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Find the symbol for an address: the closest sized symbol which covers it,
+    /// otherwise the nearest symbol at or before it
+    pub fn lookup(&self, addr: u64) -> Option<SymbolMatch<'_>> {
+        // index of the first symbol after the address
+        let end = self
+            .symbols
+            .partition_point(|symbol| symbol.address <= addr);
+        let preceding = &self.symbols[..end];
+
+        let covering = preceding
+            .iter()
+            .rev()
+            .take_while(|symbol| addr - symbol.address < self.max_size)
+            .find(|symbol| addr - symbol.address < symbol.size);
+        let nearest = match covering {
+            Some(symbol) => symbol,
+            None => preceding.last()?,
+        };
+        // among the symbols at the same address, the best ranked comes first
+        let first = preceding.partition_point(|symbol| symbol.address < nearest.address);
+        let symbol = preceding[first..]
+            .iter()
+            .find(|symbol| covering.is_none() || addr - symbol.address < symbol.size)
+            .unwrap_or(nearest);
+        Some(SymbolMatch {
+            name: &symbol.name,
+            offset: addr - symbol.address,
+            is_covering: covering.is_some(),
+            shndx: symbol.shndx,
+        })
+    }
+}
+
+impl Elf {
+    /// Describe a virtual address as `symbol+0xoffset`.
+    /// Symbols which don't cover the address are only used if they are in the same section.
+    /// Relocatable files have no addresses, so nothing is returned for them
+    pub fn symbolize(&self, addr: u64) -> Option<String> {
+        if self.hdr.e_type.0 == ET_REL {
+            return None;
+        }
+        let found = self.symbol_index.lookup(addr)?;
+        if !found.is_covering {
+            let section = self.section_by_vaddr(addr)?;
+            if section.index != found.shndx as usize {
+                return None;
+            }
+        }
+        Some(found.to_string())
+    }
+}
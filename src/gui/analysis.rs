@@ -0,0 +1,106 @@
+use native_windows_gui as nwg;
+use nwg::TreeItem;
+
+use crate::analysis::{
+    EntropyReport, EntryPoint, Exports, FileLayout, HardeningReport, Imports, ManylinuxReport,
+    PackerReport, RuntimeRequirements,
+};
+use crate::elf::Elf;
+use crate::utils::Table;
+
+// Whole file analysis methods
+impl super::ElfExplorer {
+    pub fn analysis_init_navigation_items(&self, parent: &TreeItem) {
+        let tree = &self.nav_panel_tree;
+
+        for text in [
+            "Entry Point",
+            "Hardening",
+            "Imports",
+            "Exports",
+            "Minimum Runtime",
+            "Python Extension",
+            "Segment Mapping",
+            "Section Mapping",
+            "File Layout",
+            "Entropy",
+            "Entropy Profile",
+            "Packer Detection",
+        ] {
+            tree.insert_item(text, Some(parent), nwg::TreeInsert::Last);
+        }
+    }
+
+    pub fn analysis_nav_select_event(&self, item: &TreeItem, elf: &Elf) {
+        // create a shortcut to the function which sets the field description
+        let set = |text: &str| self.field_desc.set(text);
+
+        let text = match self.nav_panel_tree.item_text(item) {
+            None => return,
+            Some(text) => text,
+        };
+
+        match &text[..] {
+            "Entry Point" => match EntryPoint::from(elf) {
+                Some(entry) => {
+                    self.report_show(&entry.to_table());
+                    set("The segment, section and symbol the entry point lands in, the bytes at the entry point and anything unusual about its location");
+                }
+                None => {
+                    let mut table = Table::new(&["Property", "Value"]);
+                    table.push(vec![
+                        String::from("Address"),
+                        String::from("None (the file has no entry point)"),
+                    ]);
+                    self.report_show(&table);
+                    set("");
+                }
+            },
+            "Hardening" => {
+                self.report_show(&HardeningReport::from(elf).to_table(elf));
+                set("Security hardening features (RELRO, NX, PIE, stack canary, FORTIFY_SOURCE, CET) and the parts of the file each verdict is based on");
+            }
+            "Imports" => {
+                self.report_show(&Imports::from(elf).to_table());
+                set("Undefined dynamic symbols, grouped by the library and version they are required from");
+            }
+            "Exports" => {
+                self.report_show(&Exports::from(elf).to_table());
+                set("Defined global and weak dynamic symbols with default or protected visibility, which other objects can link against");
+            }
+            "Minimum Runtime" => {
+                self.report_show(&RuntimeRequirements::from(elf).to_table());
+                set("Highest GLIBC_, GLIBCXX_ and CXXABI_ versions required by the file, and the imported symbols which require them");
+            }
+            "Python Extension" => {
+                self.report_show(&ManylinuxReport::from(elf).to_table());
+                set("PyInit_* functions of a CPython extension module, and the libraries and symbol versions which violate each manylinux policy");
+            }
+            "Segment Mapping" => {
+                self.report_show(&elf.segment_mapping_table());
+                set("Sections contained in each segment");
+            }
+            "Section Mapping" => {
+                self.report_show(&elf.section_mapping_table());
+                set("Segments containing each section");
+            }
+            "File Layout" => {
+                self.report_show(&FileLayout::from(elf).to_table(elf));
+                set("What covers every byte range of the file, including gaps, overlapping structures and data appended after the last structure");
+            }
+            "Entropy" => {
+                self.report_show(&EntropyReport::from(elf).to_table());
+                set("Shannon entropy (bits per byte) of every section, segment and the overlay. Executable code above 7.2 is likely packed or encrypted");
+            }
+            "Entropy Profile" => {
+                self.report_show(&EntropyReport::from(elf).profile_table());
+                set("Entropy of overlapping windows across the whole file");
+            }
+            "Packer Detection" => {
+                self.report_show(&PackerReport::from(elf).to_table());
+                set("UPX structures, and the missing section headers, RWX segments, segment sizes and entropy typical of packed or protected files");
+            }
+            _ => (),
+        }
+    }
+}
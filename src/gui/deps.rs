@@ -0,0 +1,120 @@
+use native_windows_gui as nwg;
+
+use std::fs;
+use std::path::Path;
+
+use crate::analysis::{BindingReport, DependencyTree, LdCache, SearchConfig};
+use crate::elf::{Elf, ParsingError};
+
+use super::input::InputDialog;
+
+// Dependency resolution methods
+impl super::ElfExplorer {
+    /// Ask for the root of the target filesystem and the preloaded libraries, and resolve the dependencies of the open file against it
+    fn deps_resolve(&self, title: &str) -> Option<DependencyTree> {
+        let filename = self.file_name.borrow().clone();
+        let elf = match fs::read(&filename).ok().and_then(|raw| Elf::from(raw).ok()) {
+            Some(elf) if elf.sections.dynamic().is_some() => elf,
+            _ => {
+                nwg::modal_error_message(
+                    &self.window,
+                    title,
+                    "Please open a dynamically linked file first.",
+                );
+                return None;
+            }
+        };
+
+        if !self.sysroot_dialog.run(Some(&self.window)) {
+            return None;
+        }
+        let sysroot = self
+            .sysroot_dialog
+            .get_selected_item()
+            .ok()?
+            .into_string()
+            .unwrap();
+
+        let mut config = SearchConfig::for_elf(&elf, Path::new(&sysroot));
+        config.cache = fs::read(config.host_path("/etc/ld.so.cache"))
+            .ok()
+            .and_then(|raw| LdCache::from(raw).ok());
+        // LD_PRELOAD of the target can't come from the Windows environment, so it is asked for
+        let preload = InputDialog::run(
+            &self.window,
+            title,
+            "Libraries to preload, separated by ':' or spaces (empty for none):",
+            "",
+        )?;
+        config.preload = preload
+            .split([':', ' '])
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_owned())
+            .collect();
+        Some(DependencyTree::from(Path::new(&filename), elf, &config))
+    }
+
+    pub fn deps_selection(&self) {
+        let tree = match self.deps_resolve("Resolve Dependencies") {
+            Some(tree) => tree,
+            None => return,
+        };
+        self.report_show(&tree.to_table());
+        self.field_desc.set(&format!(
+            "Libraries loaded for the file from the selected root filesystem and its /etc/ld.so.cache ({} missing, {} incompatible)",
+            tree.missing().count(),
+            tree.incompatible().count()
+        ));
+    }
+
+    pub fn binding_selection(&self) {
+        let tree = match self.deps_resolve("Resolve Symbols") {
+            Some(tree) => tree,
+            None => return,
+        };
+        let report = BindingReport::from(&tree);
+        self.report_show(&report.to_table(&tree));
+        self.field_desc.set(&format!(
+            "The library each imported symbol binds to, in the search order of the dynamic loader ({} unresolved, {} defined more than once)",
+            report.unresolved().count(),
+            report.multiply_defined().count()
+        ));
+    }
+
+    pub fn ldcache_selection(&self) {
+        if !self.ldcache_dialog.run(Some(&self.window)) {
+            return;
+        }
+        let filename = match self.ldcache_dialog.get_selected_item() {
+            Err(_) => return,
+            Ok(filename) => filename.into_string().unwrap(),
+        };
+        let raw = match fs::read(&filename) {
+            Ok(raw) => raw,
+            Err(err) => {
+                nwg::modal_error_message(&self.window, "Error Loading File", &err.to_string());
+                return;
+            }
+        };
+        let cache = match LdCache::from(raw) {
+            Ok(cache) => cache,
+            Err(err) => match err {
+                ParsingError::InvalidMagicBytes(msg)
+                | ParsingError::InvalidByteOrder(msg)
+                | ParsingError::InvalidNativeSize(msg) => {
+                    nwg::modal_error_message(&self.window, "Error parsing file", &msg);
+                    return;
+                }
+            },
+        };
+        self.report_show(&cache.to_table());
+        self.field_desc.set(&format!(
+            "{} libraries known to the dynamic loader cache{}",
+            cache.entries.len(),
+            match &cache.generator {
+                Some(generator) => format!(" (generated by {})", generator),
+                None => String::new(),
+            }
+        ));
+    }
+}
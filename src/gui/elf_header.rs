@@ -1,6 +1,6 @@
 use native_windows_gui as nwg;
 
-use crate::elf::{Description, EIdent, Elf, ElfNAddr, ElfNOff};
+use crate::elf::{Description, EIdent, Elf, ElfNAddr, ElfNOff, EIDENT_FIELDS, ELF_HEADER_FIELDS};
 use crate::{
     address_field, decimal_field, descriptive_field, hex_field, offset_field, raw_field,
     size_field, utils,
@@ -74,7 +74,7 @@ impl super::ElfExplorer {
         hex_field!("e_version", elf.hdr.e_version, list, 3);
 
         // insert e_entry field
-        address_field!("e_entry", elf.hdr.e_entry, list, 4, is_little_endian);
+        address_field!("e_entry", elf.hdr.e_entry, list, 4, elf);
 
         // insert e_phoff field
         offset_field!("e_phoff", elf.hdr.e_phoff, list, 5, is_little_endian);
@@ -83,7 +83,7 @@ impl super::ElfExplorer {
         offset_field!("e_shoff", elf.hdr.e_shoff, list, 6, is_little_endian);
 
         // insert e_flags field
-        hex_field!("e_flags", elf.hdr.e_flags, list, 7);
+        descriptive_field!("e_flags", elf.hdr.e_flags, list, 7);
 
         // insert e_ehsize field
         size_field!("e_ehsize", elf.hdr.e_ehsize, list, 8);
@@ -106,31 +106,14 @@ impl super::ElfExplorer {
 
     pub fn elf_header_select_event(&self) {
         if let Some(item) = self.elf_header_list.selected_item() {
-            // create a shortcut to the function which sets the field description
-            let set = |text: &str| self.field_desc.set(text);
-
-            match item {
-                0 => {
-                    self.e_ident_frame.set_visible(true);
-                    set("ELF identifier (inner fields displayed below)")
-                }
-                1 => set("ELF type"),
-                2 => set("Target CPU architecture"),
-                3 => set("ELF version"),
-                4 => set("Entry point (memory address)"),
-                5 => set("Program header table file offset"),
-                6 => set("Section header table file offset"),
-                7 => set("Processor specific flags"),
-                8 => set("Size of the ELF header"),
-                9 => set("Size of each program header"),
-                10 => set("Number of program headers"),
-                11 => set("Size of each section header"),
-                12 => set("Number of section headers"),
-                13 => {
-                    set("Section header table index of the entry that contains the section names")
-                }
-                _ => set(""),
+            let elf = self.elf.borrow();
+            let spans = elf.as_ref().unwrap().hdr.spans();
+
+            if item == 0 {
+                self.e_ident_frame.set_visible(true);
             }
+            self.field_desc
+                .set_field(ELF_HEADER_FIELDS.get(item), &spans);
         }
     }
 }
@@ -219,7 +202,7 @@ impl super::ElfExplorer {
         descriptive_field!("EI_OSABI", e_ident.ei_osabi, list, 4);
 
         // insert EI_ABIVERSION field
-        decimal_field!("EI_ABIVERSION", e_ident.ei_abi_version, list, 5);
+        descriptive_field!("EI_ABIVERSION", e_ident.ei_abi_version, list, 5);
 
         // insert EI_PAD field
         raw_field!("EI_PAD", e_ident.ei_pad, list, 6);
@@ -227,19 +210,10 @@ impl super::ElfExplorer {
 
     pub fn e_ident_select_event(&self) {
         if let Some(item) = self.e_ident_list.selected_item() {
-            // create a shortcut to the function which sets the field description
-            let set = |text: &str| self.field_desc.set(text);
-
-            match item {
-                0 => set("ELF magic (should be 0x7f ELF)"),
-                1 => set("ELF class (32/64 bit)"),
-                2 => set("Data format (little/big endian)"),
-                3 => set("ELF identifier version"),
-                4 => set("Operating system ABI"),
-                5 => set("ABI version"),
-                6 => set("Pad (should be all zeroes)"),
-                _ => set(""),
-            }
+            let elf = self.elf.borrow();
+            let spans = elf.as_ref().unwrap().hdr.e_ident.spans();
+
+            self.field_desc.set_field(EIDENT_FIELDS.get(item), &spans);
         }
     }
 }
@@ -0,0 +1,62 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+use nwg::NativeUi;
+
+use std::cell::RefCell;
+
+/// Modal dialog which asks for a single line of text
+#[derive(Default, NwgUi)]
+pub struct InputDialog {
+    value: RefCell<Option<String>>,
+
+    #[nwg_control(size: (420, 120), position: (300, 300), title: "", flags: "WINDOW")]
+    #[nwg_events(OnWindowClose: [InputDialog::close])]
+    window: nwg::Window,
+
+    #[nwg_control(text: "", position: (10, 10), size: (400, 20))]
+    prompt: nwg::Label,
+
+    #[nwg_control(text: "", position: (10, 35), size: (400, 25))]
+    input: nwg::TextInput,
+
+    #[nwg_control(text: "OK", position: (230, 75), size: (85, 30))]
+    #[nwg_events(OnButtonClick: [InputDialog::ok])]
+    ok_button: nwg::Button,
+
+    #[nwg_control(text: "Cancel", position: (325, 75), size: (85, 30))]
+    #[nwg_events(OnButtonClick: [InputDialog::cancel])]
+    cancel_button: nwg::Button,
+}
+
+impl InputDialog {
+    /// Show the dialog over `parent` until it is closed. Returns None if it was cancelled
+    pub fn run(parent: &nwg::Window, title: &str, prompt: &str, default: &str) -> Option<String> {
+        let dialog = InputDialog::build_ui(Default::default()).expect("Failed to build UI");
+        dialog.window.set_text(title);
+        dialog.prompt.set_text(prompt);
+        dialog.input.set_text(default);
+
+        // run a nested message loop, which the dialog stops when it is closed
+        parent.set_enabled(false);
+        dialog.window.set_visible(true);
+        dialog.input.set_focus();
+        nwg::dispatch_thread_events();
+        parent.set_enabled(true);
+        parent.set_focus();
+
+        dialog.value.take()
+    }
+
+    fn ok(&self) {
+        *self.value.borrow_mut() = Some(self.input.text());
+        self.window.close();
+    }
+
+    fn cancel(&self) {
+        self.window.close();
+    }
+
+    fn close(&self) {
+        nwg::stop_thread_dispatch();
+    }
+}
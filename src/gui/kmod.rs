@@ -0,0 +1,138 @@
+use native_windows_gui as nwg;
+use nwg::TreeItem;
+
+use std::fs;
+
+use crate::analysis::{CompatibilityReport, KernelModule, KernelSymbols};
+use crate::elf::{Elf, ParsingError};
+
+use super::input::InputDialog;
+
+// Kernel module methods
+impl super::ElfExplorer {
+    pub fn kmod_init_navigation_items(&self, parent: &TreeItem) {
+        let tree = &self.nav_panel_tree;
+
+        for text in [
+            "Module Info",
+            "Symbol Versions",
+            "Exported Symbols",
+            "Signature",
+        ] {
+            tree.insert_item(text, Some(parent), nwg::TreeInsert::Last);
+        }
+    }
+
+    pub fn kmod_select_event(&self, elf: &Elf) {
+        if let Some(kmod) = KernelModule::from(elf) {
+            self.report_show(&kmod.summary_table());
+        }
+        self.field_desc.set(
+            "Linux kernel module metadata from .modinfo, __versions and .gnu.linkonce.this_module",
+        );
+    }
+
+    pub fn kmod_nav_select_event(&self, item: &TreeItem, elf: &Elf) {
+        // create a shortcut to the function which sets the field description
+        let set = |text: &str| self.field_desc.set(text);
+
+        let kmod = match KernelModule::from(elf) {
+            None => return,
+            Some(kmod) => kmod,
+        };
+
+        let text = match self.nav_panel_tree.item_text(item) {
+            None => return,
+            Some(text) => text,
+        };
+
+        match &text[..] {
+            "Module Info" => {
+                self.report_show(&kmod.modinfo_table());
+                set("Key/value pairs from the .modinfo section (license, vermagic, depends, alias, parm...)");
+            }
+            "Symbol Versions" => {
+                self.report_show(&kmod.versions_table());
+                set("CRCs of the kernel symbols used by the module, checked against the kernel when loading (modversions)");
+            }
+            "Exported Symbols" => {
+                self.report_show(&kmod.exports_table());
+                set("Symbols exported by the module to the kernel and other modules (__ksymtab*)");
+            }
+            "Signature" => match &kmod.signature {
+                Some(signature) => {
+                    self.report_show(&signature.to_table());
+                    set("Module signature appended to the end of the file");
+                }
+                None => {
+                    self.set_all_frames_invisible();
+                    self.unimplemented_frame.set_visible(true);
+                    self.unimplemented_message
+                        .set_text("This module is not signed.");
+                    set("");
+                }
+            },
+            _ => (),
+        }
+    }
+
+    pub fn kmod_check_selection(&self) {
+        let elf = self.elf.borrow();
+        let kmod = match elf.as_ref().and_then(|elf| KernelModule::from(elf)) {
+            None => {
+                nwg::modal_error_message(
+                    &self.window,
+                    "Kernel Compatibility",
+                    "Please open a kernel module first.",
+                );
+                return;
+            }
+            Some(kmod) => kmod,
+        };
+        let elf = elf.as_ref().unwrap();
+
+        if !self.kernel_dialog.run(Some(&self.window)) {
+            return;
+        }
+        let filename = match self.kernel_dialog.get_selected_item() {
+            Err(_) => return,
+            Ok(filename) => filename.into_string().unwrap(),
+        };
+
+        let raw = match fs::read(&filename) {
+            Ok(raw) => raw,
+            Err(err) => {
+                nwg::modal_error_message(&self.window, "Error Loading File", &err.to_string());
+                return;
+            }
+        };
+        let kernel = match KernelSymbols::from(raw) {
+            Ok(kernel) => kernel,
+            Err(err) => match err {
+                ParsingError::InvalidMagicBytes(msg)
+                | ParsingError::InvalidByteOrder(msg)
+                | ParsingError::InvalidNativeSize(msg) => {
+                    nwg::modal_error_message(&self.window, "Error parsing file", &msg);
+                    return;
+                }
+            },
+        };
+
+        // Module.symvers doesn't record the vermagic of the kernel
+        let vermagic = match kernel.vermagic {
+            Some(_) => None,
+            None => InputDialog::run(
+                &self.window,
+                "Kernel vermagic",
+                "vermagic or release of the kernel (empty if unknown):",
+                "",
+            )
+            .filter(|vermagic| !vermagic.trim().is_empty()),
+        };
+        let report = CompatibilityReport::from(elf, &kmod, &kernel, vermagic.as_deref());
+        self.report_show(&report.to_table());
+        self.field_desc.set(
+            "Whether the module would load on the selected kernel (vermagic, symbol CRCs and unresolved symbols)",
+        );
+    }
+}
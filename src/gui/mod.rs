@@ -11,9 +11,14 @@ use std::io::Read;
 
 use crate::elf;
 
+mod analysis;
+mod deps;
 mod elf_header;
+mod input;
+mod kmod;
 mod nav_panel;
 mod pheaders;
+mod report;
 mod sections;
 mod sheaders;
 
@@ -45,6 +50,31 @@ pub struct ElfExplorer {
     #[nwg_events(OnMenuItemSelected: [ElfExplorer::file_selection])]
     file_open: nwg::MenuItem,
 
+    #[nwg_resource(title: "Select Module.symvers or vmlinux", action: nwg::FileDialogAction::Open)]
+    kernel_dialog: nwg::FileDialog,
+
+    #[nwg_control(parent: file_menu, text: "Check Kernel Compatibility...")]
+    #[nwg_events(OnMenuItemSelected: [ElfExplorer::kmod_check_selection])]
+    file_kmod_check: nwg::MenuItem,
+
+    #[nwg_resource(title: "Select the root of the target filesystem", action: nwg::FileDialogAction::OpenDirectory)]
+    sysroot_dialog: nwg::FileDialog,
+
+    #[nwg_control(parent: file_menu, text: "Resolve Dependencies...")]
+    #[nwg_events(OnMenuItemSelected: [ElfExplorer::deps_selection])]
+    file_deps: nwg::MenuItem,
+
+    #[nwg_control(parent: file_menu, text: "Resolve Symbols...")]
+    #[nwg_events(OnMenuItemSelected: [ElfExplorer::binding_selection])]
+    file_binding: nwg::MenuItem,
+
+    #[nwg_resource(title: "Select ld.so.cache", action: nwg::FileDialogAction::Open)]
+    ldcache_dialog: nwg::FileDialog,
+
+    #[nwg_control(parent: file_menu, text: "View ld.so.cache...")]
+    #[nwg_events(OnMenuItemSelected: [ElfExplorer::ldcache_selection])]
+    file_ldcache: nwg::MenuItem,
+
     #[nwg_control(parent: file_menu)]
     file_separator: nwg::MenuSeparator,
 
@@ -159,6 +189,26 @@ pub struct ElfExplorer {
 
     #[nwg_control(parent: strtab_frame, position: (0, 0), size: (600, 580), item_count: 1, list_style: ListViewStyle::Detailed, flags: "VISIBLE", ex_flags: EX_FLAGS)]
     strtab_list: nwg::ListView,
+
+    // Symtab section view
+    #[nwg_control(position: (200, 0), size: (600, 580), flags: "NONE")]
+    symtab_frame: nwg::Frame,
+
+    #[nwg_layout(parent: symtab_frame)]
+    symtab_layout: nwg::DynLayout,
+
+    #[nwg_control(parent: symtab_frame, position: (0, 0), size: (600, 580), item_count: 1, list_style: ListViewStyle::Detailed, flags: "VISIBLE", ex_flags: EX_FLAGS)]
+    symtab_list: nwg::ListView,
+
+    // Report view
+    #[nwg_control(position: (200, 0), size: (600, 580), flags: "NONE")]
+    report_frame: nwg::Frame,
+
+    #[nwg_layout(parent: report_frame)]
+    report_layout: nwg::DynLayout,
+
+    #[nwg_control(parent: report_frame, position: (0, 0), size: (600, 580), item_count: 1, list_style: ListViewStyle::Detailed, flags: "VISIBLE", ex_flags: EX_FLAGS)]
+    report_list: nwg::ListView,
 }
 
 impl ElfExplorer {
@@ -191,6 +241,10 @@ impl ElfExplorer {
             .add_child((0, 0), (100, 100), &self.unimplemented_frame);
         self.main_layout
             .add_child((0, 0), (100, 100), &self.strtab_frame);
+        self.main_layout
+            .add_child((0, 0), (100, 100), &self.symtab_frame);
+        self.main_layout
+            .add_child((0, 0), (100, 100), &self.report_frame);
 
         self.nav_panel_init();
         self.field_desc.init(&self.field_desc_frame);
@@ -199,6 +253,8 @@ impl ElfExplorer {
         self.pheaders_init();
         self.sheaders_init();
         self.strtab_init();
+        self.symtab_init();
+        self.report_init();
     }
 
     pub fn set_all_frames_invisible(&self) {
@@ -209,6 +265,8 @@ impl ElfExplorer {
         self.pheaders_frame.set_visible(false);
         self.sheaders_frame.set_visible(false);
         self.strtab_frame.set_visible(false);
+        self.symtab_frame.set_visible(false);
+        self.report_frame.set_visible(false);
     }
 
     fn init_elf_view(&self) {
@@ -370,4 +428,18 @@ impl FieldDesc {
     fn set(&self, text: &str) {
         self.description.set_text(text);
     }
+
+    // describe a header field along with the location of its bytes in the file
+    fn set_field(&self, field: Option<&elf::FieldInfo>, spans: &[elf::FieldSpan]) {
+        match field {
+            None => self.set(""),
+            Some(field) => match elf::span_of(spans, field.name) {
+                Some(span) => self.set(&format!(
+                    "{} [offset 0x{:x}, {} bytes]",
+                    field.short, span.offset, span.size
+                )),
+                None => self.set(field.short),
+            },
+        }
+    }
 }
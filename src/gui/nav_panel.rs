@@ -1,7 +1,10 @@
 use native_windows_gui as nwg;
 use nwg::TreeItem;
 
-use crate::elf::Elf;
+use crate::analysis::KernelModule;
+use crate::elf::{
+    Elf, ELF_HEADER_DESCRIPTION, PROGRAM_HEADERS_DESCRIPTION, SECTION_HEADERS_DESCRIPTION,
+};
 
 // Nav panel methods
 impl super::ElfExplorer {
@@ -29,6 +32,14 @@ impl super::ElfExplorer {
         let sheaders = tv.insert_item("Section Headers", None, nwg::TreeInsert::Root);
 
         self.sheaders_init_navigation_items(&sheaders, elf);
+
+        let analysis = tv.insert_item("Analysis", None, nwg::TreeInsert::Root);
+        self.analysis_init_navigation_items(&analysis);
+
+        if KernelModule::is_kernel_module(elf) {
+            let kmod = tv.insert_item("Kernel Module", None, nwg::TreeInsert::Root);
+            self.kmod_init_navigation_items(&kmod);
+        }
     }
 
     pub fn nav_panel_select_event(&self) {
@@ -61,7 +72,7 @@ impl super::ElfExplorer {
                         }
                         self.set_all_frames_invisible();
                         self.elf_header_frame.set_visible(true);
-                        set("The ELF header contains general information as well as the locations of the program and section header tables");
+                        set(ELF_HEADER_DESCRIPTION);
                     }
                     "Program Headers" => {
                         if !self.pheaders_frame.visible() {
@@ -70,7 +81,7 @@ impl super::ElfExplorer {
                         }
                         self.set_all_frames_invisible();
                         self.pheaders_frame.set_visible(true);
-                        set("Program headers contain segments which describe the memory layout of the program and are necessary for loading it");
+                        set(PROGRAM_HEADERS_DESCRIPTION);
                     }
                     "Section Headers" => {
                         if !self.sheaders_frame.visible() {
@@ -79,8 +90,10 @@ impl super::ElfExplorer {
                         }
                         self.set_all_frames_invisible();
                         self.sheaders_frame.set_visible(true);
-                        set("Section headers contain linking and debugging information");
+                        set(SECTION_HEADERS_DESCRIPTION);
                     }
+                    "Analysis" => set("Summaries and checks computed from the whole file"),
+                    "Kernel Module" => self.kmod_select_event(elf),
                     _ => set(""),
                 }
             }
@@ -92,6 +105,8 @@ impl super::ElfExplorer {
 
                 match &text[..] {
                     "Section Headers" => self.section_nav_select_event(&item, elf),
+                    "Analysis" => self.analysis_nav_select_event(&item, elf),
+                    "Kernel Module" => self.kmod_nav_select_event(&item, elf),
                     _ => (),
                 }
             }
@@ -1,6 +1,9 @@
 use native_windows_gui as nwg;
 
-use crate::elf::{Description, Elf, ElfNAddr, ElfNOff, ProgramHeader32, ProgramHeader64};
+use crate::elf::{
+    field, Description, Elf, ElfNAddr, ElfNOff, ProgramHeader32, ProgramHeader64,
+    PROGRAM_HEADER_FIELDS,
+};
 use crate::{address_field, decimal_field, descriptive_field, offset_field, size_field, utils};
 
 // Program header table methods
@@ -102,20 +105,12 @@ impl super::ElfExplorer {
                 }
             }
 
-            let desc = match p_type {
-                 0 => "NULL headers should be ignored",
-                 1 => "LOAD headers specify a segment of the program that should be loaded into memory",
-                 2 => "DYNAMIC headers specify information for the dynamic linker",
-                 3 => "INTERP headers specify the filesystem path of the dynamic linker",
-                 4 => "NOTE headers contain system specific information used for various purposes",
-                 5 => "SHLIB headers are reserved and currently save no purpose",
-                 6 => "A PHDR header specifies the location of the program header table itself in the file and in memory, if it is to be loaded into memory",
-                 7 => "TLS headers specify a thread-local storage section",
-                 0x6474e550 => "GNU_EH_FRAME headers specify exception handler information for GCC",
-                 0x6474e551 => "GNU_STACK headers specify whether the stack should have execute permissions",
-                 0x6474e552 => "GNU_RELRO headers spceify which parts of memory should be marked as read-only after relocation",
-                 0x6474e553 => "GNU_PROPERTY headers specify special handling requirements for the kernel and dynamic linker",
-                 _ => "Unknown program header type"
+            let desc = match field(PROGRAM_HEADER_FIELDS, "p_type")
+                .unwrap()
+                .value(p_type as u64)
+            {
+                Some(value) => value.description,
+                None => "Unknown program header type",
             };
             self.field_desc.set(desc);
 
@@ -172,10 +167,10 @@ impl super::ElfExplorer {
         offset_field!("p_offset", phdr.p_offset, list, 1, is_little_endian);
 
         // insert p_vaddr field
-        address_field!("p_vaddr", phdr.p_vaddr, list, 2, is_little_endian);
+        address_field!("p_vaddr", phdr.p_vaddr, list, 2, elf);
 
         // insert p_paddr field
-        address_field!("p_paddr", phdr.p_paddr, list, 3, is_little_endian);
+        address_field!("p_paddr", phdr.p_paddr, list, 3, elf);
 
         // insert p_filesz field
         size_field!("p_filesz", phdr.p_filesz, list, 4);
@@ -204,10 +199,10 @@ impl super::ElfExplorer {
         offset_field!("p_offset", phdr.p_offset, list, 2, is_little_endian);
 
         // insert p_vaddr field
-        address_field!("p_vaddr", phdr.p_vaddr, list, 3, is_little_endian);
+        address_field!("p_vaddr", phdr.p_vaddr, list, 3, elf);
 
         // insert p_paddr field
-        address_field!("p_paddr", phdr.p_paddr, list, 4, is_little_endian);
+        address_field!("p_paddr", phdr.p_paddr, list, 4, elf);
 
         // insert p_filesz field
         size_field!("p_filesz", phdr.p_filesz, list, 5);
@@ -221,11 +216,18 @@ impl super::ElfExplorer {
 
     pub fn phdr_select_event(&self) {
         if let Some(mut item) = self.phdr_list.selected_item() {
-            // create a shortcut to the function which sets the field description
-            let set = |text: &str| self.field_desc.set(text);
+            let elf = self.elf.borrow();
+            let elf = elf.as_ref().unwrap();
+
+            // locate the fields of the selected program header
+            let index = self.pheaders_list.selected_item().unwrap_or(0);
+            let spans = match elf.is_64_bit() {
+                true => elf.phdr_table.phdrs64.as_ref().unwrap()[index].spans(),
+                false => elf.phdr_table.phdrs32.as_ref().unwrap()[index].spans(),
+            };
 
             // adjust 64 bit field index to match 32 bit order of fields
-            let is_64_bit = (&*self.elf.borrow().as_ref().unwrap()).is_64_bit();
+            let is_64_bit = elf.is_64_bit();
             if is_64_bit && item == 1 {
                 item = 6;
             } else if is_64_bit && item >= 2 && item <= 6 {
@@ -233,17 +235,8 @@ impl super::ElfExplorer {
             }
 
             // set description based on field order of 32 bit pheader
-            match item {
-                0 => set("Program header type"),
-                1 => set("Segment offset into the file"),
-                2 => set("Segment loading address (virtual)"),
-                3 => set("Segment loading address (physical)"),
-                4 => set("Segment size in the file"),
-                5 => set("Segment size in memory"),
-                6 => set("Segment permissions (R=Read, W=Write, E=Execute)"),
-                7 => set("Alignment in memory"),
-                _ => set(""),
-            };
+            self.field_desc
+                .set_field(PROGRAM_HEADER_FIELDS.get(item), &spans);
         }
     }
 }
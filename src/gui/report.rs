@@ -0,0 +1,57 @@
+use native_windows_gui as nwg;
+
+use crate::utils::Table;
+
+// Generic report view methods
+impl super::ElfExplorer {
+    pub fn report_init(&self) {
+        self.report_list.set_headers_enabled(true);
+        self.report_layout
+            .add_child((0, 0), (100, 100), &self.report_list);
+    }
+
+    fn report_init_columns(&self, table: &Table) {
+        while self.report_list.column_len() > 0 {
+            self.report_list.remove_column(0);
+        }
+
+        // the first column usually holds a short key, the rest share the remaining width
+        let count = table.columns.len() as i32;
+        for (i, text) in table.columns.iter().enumerate() {
+            let width = match (i, count) {
+                (_, 1) => 580,
+                (0, _) => 150,
+                (_, count) => 430 / (count - 1),
+            };
+            let column = nwg::InsertListViewColumn {
+                index: Some(i as i32),
+                fmt: None,
+                width: Some(width),
+                text: Some(text.to_owned()),
+            };
+            self.report_list.insert_column(column);
+        }
+    }
+
+    pub fn report_populate(&self, table: &Table) {
+        self.report_list.clear();
+        self.report_init_columns(table);
+
+        for (i, row) in table.rows.iter().enumerate() {
+            for (j, text) in row.iter().enumerate() {
+                let item = nwg::InsertListViewItem {
+                    index: Some(i as i32),
+                    column_index: j as i32,
+                    text: Some(text.to_owned()),
+                };
+                self.report_list.insert_item(item);
+            }
+        }
+    }
+
+    pub fn report_show(&self, table: &Table) {
+        self.set_all_frames_invisible();
+        self.report_populate(table);
+        self.report_frame.set_visible(true);
+    }
+}
@@ -1,10 +1,12 @@
 use native_windows_gui as nwg;
 use nwg::TreeItem;
 
+use crate::analysis::MipsGot;
 use crate::elf::sections::SectionType;
 use crate::elf::Elf;
 
 mod strtab;
+mod symtab;
 
 impl super::ElfExplorer {
     pub fn section_nav_select_event(&self, item: &TreeItem, elf: &Elf) {
@@ -37,9 +39,62 @@ impl super::ElfExplorer {
                 self.strtab_populate(strtab);
                 self.strtab_frame.set_visible(true);
             }
-            SectionType::Generic => {
-                self.section_unimplemented(&section.type_name());
+            SectionType::Symtab(symtab) => {
+                self.set_all_frames_invisible();
+                self.symtab_populate(elf, symtab);
+                self.symtab_frame.set_visible(true);
+            }
+            SectionType::Attributes(attributes) => {
+                self.report_show(&attributes.to_table());
+            }
+            SectionType::Dynamic(dynamic) => {
+                let mut table = dynamic.to_table();
+                let symbols = dynamic
+                    .entries
+                    .iter()
+                    .map(|entry| match entry.d_tag.is_address() {
+                        true => elf.symbolize(entry.d_val).unwrap_or_default(),
+                        false => String::new(),
+                    });
+                table.insert_column(2, "Symbol", symbols);
+                self.report_show(&table);
+            }
+            SectionType::MipsAbiFlags(abiflags) => {
+                self.report_show(&abiflags.to_table());
+            }
+            SectionType::MipsRegInfo(reginfo) => {
+                self.report_show(&reginfo.to_table());
+            }
+            SectionType::MipsOptions(options) => {
+                self.report_show(&options.to_table());
+            }
+            SectionType::Relocation(relocations) => {
+                // the symbol containing each relocated location
+                let mut table = relocations.to_table();
+                let locations = relocations
+                    .relocations
+                    .iter()
+                    .map(|reloc| elf.symbolize(reloc.r_offset).unwrap_or_default());
+                table.insert_column(1, "Location", locations);
+                self.report_show(&table);
+            }
+            SectionType::Note(notes) => {
+                self.report_show(&notes.to_table());
+            }
+            SectionType::Versym(versym) => {
+                self.report_show(&versym.to_table(&elf.sections));
+            }
+            SectionType::Verneed(verneed) => {
+                self.report_show(&verneed.to_table());
+            }
+            SectionType::Verdef(verdef) => {
+                self.report_show(&verdef.to_table());
             }
+            SectionType::Generic => match MipsGot::from(elf) {
+                // the MIPS GOT is a regular PROGBITS section, identified by DT_PLTGOT
+                Some(got) if section.addr == got.address => self.report_show(&got.to_table()),
+                _ => self.section_unimplemented(&section.type_name()),
+            },
         }
     }
 
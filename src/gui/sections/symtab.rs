@@ -0,0 +1,70 @@
+use native_windows_gui as nwg;
+
+use crate::elf::sections::SymtabSection;
+use crate::elf::{Description, Elf};
+use crate::gui::ElfExplorer;
+
+impl ElfExplorer {
+    pub fn symtab_init(&self) {
+        self.symtab_list.set_headers_enabled(true);
+        self.symtab_layout
+            .add_child((0, 0), (100, 100), &self.symtab_list);
+        self.symtab_init_columns();
+    }
+
+    fn symtab_init_columns(&self) {
+        let columns = [
+            ("Index", 50),
+            ("Value", 110),
+            ("Location", 150),
+            ("Size", 60),
+            ("Type", 70),
+            ("Bind", 60),
+            ("Visibility", 70),
+            ("Ndx", 50),
+            ("Name", 300),
+        ];
+        for (i, (text, width)) in columns.iter().enumerate() {
+            let column = nwg::InsertListViewColumn {
+                index: Some(i as i32),
+                fmt: None,
+                width: Some(*width),
+                text: Some(text.to_string()),
+            };
+            self.symtab_list.insert_column(column);
+        }
+    }
+
+    pub fn symtab_populate(&self, elf: &Elf, symtab: &SymtabSection) {
+        self.symtab_list.clear();
+
+        for (i, symbol) in symtab.symbols.iter().enumerate() {
+            // the symbol the value resolves to, left out when it is the symbol itself
+            let location = match symbol.is_undefined() {
+                true => None,
+                false => elf.symbolize(symbol.st_value),
+            }
+            .filter(|location| *location != symbol.name_or_empty())
+            .unwrap_or_default();
+            let values = [
+                format!("{}", symbol.index),
+                format!("0x{:x}", symbol.st_value),
+                location,
+                format!("{}", symbol.st_size),
+                symbol.sym_type().to_str(),
+                symbol.bind().to_str(),
+                symbol.visibility().to_str(),
+                symbol.st_shndx.to_str(),
+                symbol.name_or_empty().to_owned(),
+            ];
+            for (j, text) in values.iter().enumerate() {
+                let item = nwg::InsertListViewItem {
+                    index: Some(i as i32),
+                    column_index: j as i32,
+                    text: Some(text.to_owned()),
+                };
+                self.symtab_list.insert_item(item);
+            }
+        }
+    }
+}
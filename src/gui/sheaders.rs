@@ -1,7 +1,10 @@
 use native_windows_gui as nwg;
 use nwg::TreeItem;
 
-use crate::elf::{Description, Elf, ElfNAddr, ElfNOff, SectionHeader32, SectionHeader64};
+use crate::elf::{
+    field, Description, Elf, ElfNAddr, ElfNOff, SHType, SectionHeader32, SectionHeader64,
+    SECTION_HEADER_FIELDS,
+};
 use crate::{
     address_field, decimal_field, descriptive_field, hex_field, offset_field, size_field, utils,
 };
@@ -169,26 +172,11 @@ impl super::ElfExplorer {
                 }
             }
 
-            let desc = match sh_type {
-                0 => "NULL represents an entry with no associated section",
-                1 => "PROGBITS sections contain information defined by the program",
-                2 => "SYMTAB sections hold a symbol table",
-                3 => "STRTAB sections hold a string table",
-                4 => "RELA sections hold relocation entries with explicit addends",
-                5 => "HASH sections hold a symbol hash table, which is required for dynamically linked files",
-                6 => "DYNAMIC sections contain information for dynamic linking",
-                7 => "NOTE sections contain special information that marks the file in some way",
-                8 => "NOBITS sections are similar to PROGBITS sections, but occupy no space in the file",
-                9 => "REL sections hold relocation entries without explicit addends",
-                10 => "SHLIB identify reserved sections",
-                11 => "DYNSYM sections hold a minimal symbol table",
-                14 => "INIT_ARRAY sections hold a set of pointers to initialization functions",
-                15 => "FINI_ARRAY sections hold a set of pointers to termination functions",
-                0x6ffffff6 => "GNU_HASH sections hold a GNU style symbol hash table",
-                0x6ffffffd => "VERDEF sections contain versioning information",
-                0x6ffffffe => "VERNEED sections contain dependency information",
-                0x6fffffff => "VERSYM sections describe the relation between symbols and version information",
-                _ => "Unknown section header type"
+            let desc = match field(SECTION_HEADER_FIELDS, "sh_type").unwrap().value(sh_type as u64) {
+                Some(value) => value.description,
+                None if SHType(sh_type, elf.hdr.e_machine.0).is_attributes() => "ATTRIBUTES sections hold build attributes which describe the target architecture and ABI",
+                None if (0x70000000..=0x7fffffff).contains(&sh_type) => "Processor specific section type (interpretation depends on the target architecture)",
+                None => "Unknown section header type",
             };
             self.field_desc.set(desc);
 
@@ -269,7 +257,7 @@ impl super::ElfExplorer {
         descriptive_field!("sh_flags", shdr.sh_flags, list, 2);
 
         // insert sh_addr field
-        address_field!("sh_addr", shdr.sh_addr, list, 3, is_little_endian);
+        address_field!("sh_addr", shdr.sh_addr, list, 3, elf);
 
         // insert sh_offset field
         offset_field!("sh_offset", shdr.sh_offset, list, 4, is_little_endian);
@@ -325,7 +313,7 @@ impl super::ElfExplorer {
         descriptive_field!("sh_flags", shdr.sh_flags, list, 2);
 
         // insert sh_addr field
-        address_field!("sh_addr", shdr.sh_addr, list, 3, is_little_endian);
+        address_field!("sh_addr", shdr.sh_addr, list, 3, elf);
 
         // insert sh_offset field
         offset_field!("sh_offset", shdr.sh_offset, list, 4, is_little_endian);
@@ -348,23 +336,19 @@ impl super::ElfExplorer {
 
     pub fn shdr_select_event(&self) {
         if let Some(item) = self.shdr_list.selected_item() {
-            // create a shortcut to the function which sets the field description
-            let set = |text: &str| self.field_desc.set(text);
-
-            // set description based on field order of 32 bit pheader
-            match item {
-                0 => set("Section name"),
-                1 => set("Section type"),
-                2 => set("Section flags (W=Write, A=Alloc, X=Execute, I=Info Link)"),
-                3 => set("Section address in memory"),
-                4 => set("Section offset in file"),
-                5 => set("Section size"),
-                6 => set("Section header table link (interpretation depends on section type)"),
-                7 => set("Extra info (interpretation depends on section type)"),
-                8 => set("Address alignment of section"),
-                9 => set("Entry size (for sections that hold a table of some sort)"),
-                _ => set(""),
+            let elf = self.elf.borrow();
+            let elf = elf.as_ref().unwrap();
+
+            // locate the fields of the selected section header
+            let index = self.sheaders_list.selected_item().unwrap_or(0);
+            let spans = match elf.is_64_bit() {
+                true => elf.shdr_table.shdrs64.as_ref().unwrap()[index].spans(),
+                false => elf.shdr_table.shdrs32.as_ref().unwrap()[index].spans(),
             };
+
+            // set description based on field order of section header
+            self.field_desc
+                .set_field(SECTION_HEADER_FIELDS.get(item), &spans);
         }
     }
 }
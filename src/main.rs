@@ -1,5 +1,6 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+pub mod analysis;
 pub mod elf;
 pub mod gui;
 pub mod utils;
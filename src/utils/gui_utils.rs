@@ -24,15 +24,15 @@ macro_rules! descriptive_field {
 
 #[macro_export]
 macro_rules! address_field {
-    ($name:expr, $val:expr, $list:expr, $row:expr, $is_little_endian:expr) => {
+    ($name:expr, $val:expr, $list:expr, $row:expr, $elf:expr) => {
         let field = nwg::InsertListViewItem {
             index: Some($row),
             column_index: 0,
             text: Some($name.to_owned()),
         };
-        let text = match $val {
-            ElfNAddr::Elf32Addr(val) => format!("0x{:x}", val),
-            ElfNAddr::Elf64Addr(val) => format!("0x{:x}", val),
+        let text = match $elf.symbolize($val.to_u64()) {
+            Some(symbol) => format!("0x{:x} ({})", $val.to_u64(), symbol),
+            None => format!("0x{:x}", $val.to_u64()),
         };
         let value = nwg::InsertListViewItem {
             index: Some($row),
@@ -40,8 +40,8 @@ macro_rules! address_field {
             text: Some(text),
         };
         let text = match $val {
-            ElfNAddr::Elf32Addr(val) => utils::u32_to_hex(val, $is_little_endian),
-            ElfNAddr::Elf64Addr(val) => utils::u64_to_hex(val, $is_little_endian),
+            ElfNAddr::Elf32Addr(val) => utils::u32_to_hex(val, $elf.is_little_endian()),
+            ElfNAddr::Elf64Addr(val) => utils::u64_to_hex(val, $elf.is_little_endian()),
         };
         let data = nwg::InsertListViewItem {
             index: Some($row),
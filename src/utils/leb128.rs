@@ -0,0 +1,37 @@
+/// Read an unsigned LEB128 value, advancing `offset` past it
+pub fn read_uleb128(data: &[u8], offset: &mut usize) -> Option<u64> {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*offset)?;
+        *offset += 1;
+        if shift < 64 {
+            result |= ((byte & 0x7f) as u64) << shift;
+        }
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+}
+
+/// Read a signed LEB128 value, advancing `offset` past it
+pub fn read_sleb128(data: &[u8], offset: &mut usize) -> Option<i64> {
+    let mut result: i64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*offset)?;
+        *offset += 1;
+        if shift < 64 {
+            result |= ((byte & 0x7f) as i64) << shift;
+        }
+        shift += 7;
+        if byte & 0x80 == 0 {
+            // sign extend
+            if shift < 64 && byte & 0x40 != 0 {
+                result |= -1 << shift;
+            }
+            return Some(result);
+        }
+    }
+}
@@ -4,3 +4,7 @@ mod conversions;
 pub use conversions::*;
 mod gui_utils;
 pub use gui_utils::*;
+mod leb128;
+pub use leb128::*;
+mod table;
+pub use table::*;
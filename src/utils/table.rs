@@ -0,0 +1,32 @@
+/// A generic tabular report which can be displayed by any frontend
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// Insert a column before `index`, with one value per row
+    pub fn insert_column(
+        &mut self,
+        index: usize,
+        name: &str,
+        values: impl IntoIterator<Item = String>,
+    ) {
+        self.columns.insert(index, name.to_owned());
+        let mut values = values.into_iter();
+        for row in &mut self.rows {
+            row.insert(index, values.next().unwrap_or_default());
+        }
+    }
+}